@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+/// What a character is doing, independent of how its sprite sheet names the animation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AnimState {
+    Idle,
+    Move,
+    Attack,
+    Hurt,
+    Death,
+}
+
+impl AnimState {
+    // played once on top of the current state, which resumes afterwards
+    pub fn is_one_shot(&self) -> bool {
+        matches!(self, AnimState::Hurt)
+    }
+
+    // a one shot only interrupts states below it, nothing interrupts death
+    fn priority(&self) -> u8 {
+        match self {
+            AnimState::Idle | AnimState::Move | AnimState::Attack => 0,
+            AnimState::Hurt => 1,
+            AnimState::Death => 2,
+        }
+    }
+}
+
+/// Maps logical states to Aseprite tag names and decides which one plays.
+/// Lives next to the `SpriteAnimator` it drives.
+#[derive(Component)]
+pub struct AnimationController {
+    tags: &'static [(AnimState, &'static str)],
+    state: AnimState,
+    one_shot: Option<AnimState>,
+    // the state currently shown by the animator, None forces a restart
+    pub(super) applied: Option<AnimState>,
+}
+
+impl AnimationController {
+    pub fn new(tags: &'static [(AnimState, &'static str)]) -> Self {
+        Self {
+            tags,
+            state: AnimState::Idle,
+            one_shot: None,
+            applied: None,
+        }
+    }
+
+    /// The state that should be on screen right now
+    pub fn current(&self) -> AnimState {
+        self.one_shot.unwrap_or(self.state)
+    }
+
+    /// Sets the looping state, a running one shot keeps playing on top of it
+    pub fn set_state(&mut self, state: AnimState) {
+        if self.state == AnimState::Death || state.is_one_shot() {
+            return;
+        }
+        if state == AnimState::Death {
+            self.one_shot = None;
+        }
+        self.state = state;
+    }
+
+    /// Plays a one shot animation, restarting it if it is already playing
+    pub fn play_once(&mut self, state: AnimState) {
+        if !state.is_one_shot() || self.current().priority() > state.priority() {
+            return;
+        }
+        self.one_shot = Some(state);
+        self.applied = None;
+    }
+
+    pub fn finish_one_shot(&mut self) {
+        self.one_shot = None;
+    }
+
+    pub fn is_playing_one_shot(&self) -> bool {
+        self.one_shot.is_some()
+    }
+
+    /// The tag for a state, states the sheet does not export fall back to idle
+    pub fn tag(&self, state: AnimState) -> &'static str {
+        self.find_tag(state)
+            .or_else(|| self.find_tag(AnimState::Idle))
+            .unwrap_or_default()
+    }
+
+    fn find_tag(&self, state: AnimState) -> Option<&'static str> {
+        self.tags
+            .iter()
+            .find(|(tag_state, _)| *tag_state == state)
+            .map(|(_, tag)| *tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TAGS: &[(AnimState, &str)] = &[
+        (AnimState::Idle, "idle"),
+        (AnimState::Move, "walk"),
+        (AnimState::Hurt, "hurt"),
+        (AnimState::Death, "death"),
+    ];
+
+    #[test]
+    fn one_shot_returns_to_latest_state() {
+        let mut controller = AnimationController::new(TAGS);
+        controller.set_state(AnimState::Move);
+        controller.play_once(AnimState::Hurt);
+        assert_eq!(controller.current(), AnimState::Hurt);
+
+        // the state underneath keeps following the character
+        controller.set_state(AnimState::Idle);
+        assert_eq!(controller.current(), AnimState::Hurt);
+
+        controller.finish_one_shot();
+        assert_eq!(controller.current(), AnimState::Idle);
+    }
+
+    #[test]
+    fn death_is_final() {
+        let mut controller = AnimationController::new(TAGS);
+        controller.play_once(AnimState::Hurt);
+        controller.set_state(AnimState::Death);
+        assert_eq!(controller.current(), AnimState::Death);
+
+        controller.play_once(AnimState::Hurt);
+        controller.set_state(AnimState::Move);
+        assert_eq!(controller.current(), AnimState::Death);
+    }
+
+    #[test]
+    fn missing_tags_fall_back_to_idle() {
+        let controller = AnimationController::new(TAGS);
+        assert_eq!(controller.tag(AnimState::Attack), "idle");
+        assert_eq!(controller.tag(AnimState::Move), "walk");
+    }
+}
@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_asepritesheet::prelude::*;
+
+use super::components::*;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (finish_one_shots, apply_animation)
+                .chain()
+                .after(AnimationSet),
+        );
+    }
+}
+
+/// Systems that pick animation states run in this set, before they are applied
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnimationSet;
+
+fn finish_one_shots(
+    mut animation_events: EventReader<AnimFinishEvent>,
+    mut query: Query<(&mut AnimationController, &Handle<Spritesheet>)>,
+    spritesheets: Res<Assets<Spritesheet>>,
+) {
+    for event in animation_events.read() {
+        let Ok((mut controller, sheet_handle)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        let Some(sheet) = spritesheets.get(sheet_handle) else {
+            continue;
+        };
+
+        // only the one shot itself ending hands control back
+        let state = controller.current();
+        if state.is_one_shot() && event.anim == sheet.get_anim_handle(controller.tag(state)) {
+            controller.finish_one_shot();
+        }
+    }
+}
+
+fn apply_animation(
+    mut query: Query<(
+        &mut AnimationController,
+        &mut SpriteAnimator,
+        &Handle<Spritesheet>,
+    )>,
+    spritesheets: Res<Assets<Spritesheet>>,
+) {
+    for (mut controller, mut animator, sheet_handle) in &mut query {
+        let state = controller.current();
+        if controller.applied == Some(state) {
+            continue;
+        }
+        let Some(sheet) = spritesheets.get(sheet_handle) else {
+            continue;
+        };
+
+        animator.set_anim(sheet.get_anim_handle(controller.tag(state)));
+        if state.is_one_shot() {
+            animator.restart_anim();
+        }
+        controller.applied = Some(state);
+    }
+}
@@ -1,36 +1,95 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
 
+// Each channel has its own volume and can be paused or stopped on its own
 #[derive(Resource)]
-pub struct SoundEffectsAudioChannel;
+pub struct MusicChannel;
 
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Resource)]
+pub struct UiChannel;
+
+// Stingers play over the music while it is ducked
+#[derive(Resource)]
+pub struct StingerChannel;
+
+// Stems of the game track, each one has its own volume instead of the channel's
+#[derive(Resource)]
+pub struct StemChannel;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundChannel {
+    Music,
+    Sfx,
+    Ui,
+}
+
+/// Plays `sound`, panned and quieter the further `position` is from the
+/// camera. Sounds without a position play centered, like the player's own.
 #[derive(Event)]
 pub struct PlaySoundEffectEvent {
     pub sound: SoundEffectType,
+    pub position: Option<Vec2>,
 }
 
+/// Something happened in the game that can make a sound. Which sound effects
+/// play for it is looked up in the `cues` table of `sound_effects.ron`.
+#[derive(Event)]
+pub struct SoundCueEvent {
+    pub cue: SoundCue,
+    pub position: Option<Vec2>,
+}
+
+/// Crossfades from the current track to `sound` over `crossfade` ms. With
+/// `resume` the game track picks up where it was left instead of restarting.
 #[derive(Event)]
 pub struct PlayMusicEvent {
     pub sound: MusicType,
     pub looping: bool,
-    pub fade_in: u64,
+    pub crossfade: u64,
+    pub resume: bool,
+}
+
+/// A short musical cue played over ducked music
+#[derive(Event)]
+pub struct PlayStingerEvent {
+    pub stinger: StingerType,
 }
 
+/// Stops everything playing on one channel, fading out over `fade_out` ms
 #[derive(Event)]
-pub struct StopSoundEvent;
+pub struct StopSoundEvent {
+    pub channel: SoundChannel,
+    pub fade_out: u64,
+}
 
 impl Default for PlayMusicEvent {
     fn default() -> Self {
         Self {
             sound: MusicType::Menu,
             looping: false,
-            fade_in: 0,
+            crossfade: 1000,
+            resume: false,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum SoundCue {
+    PlayerShot,
+    PlayerHit,
+    PlayerDied,
+    EnemyHit,
+    EnemyDied,
+    BulletImpact,
+    XPCollected,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum SoundEffectType {
     PlayerShoot,
     PlayerHurt,
@@ -38,41 +97,74 @@ pub enum SoundEffectType {
     EnemyWalk,
     EnemyHurt,
     EnemyDeath,
+    BulletImpact,
     XPCollect,
     UIHover,
     UIEnter,
-    EnterLevelUp,
 }
 
-#[derive(Debug)]
+impl SoundEffectType {
+    pub fn channel(&self) -> SoundChannel {
+        match self {
+            SoundEffectType::UIHover | SoundEffectType::UIEnter => SoundChannel::Ui,
+            _ => SoundChannel::Sfx,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MusicType {
     Game,
     Menu,
     Upgrade,
 }
 
+impl MusicType {
+    // only the game track is paused instead of stopped when another one starts
+    pub fn resumable(&self) -> bool {
+        matches!(self, MusicType::Game)
+    }
+
+    pub fn has_stems(&self) -> bool {
+        matches!(self, MusicType::Game)
+    }
+}
+
+/// Layers played in sync over the game track, faded in as intensity rises
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MusicStem {
+    Drums,
+    Bass,
+    Lead,
+}
+
+impl MusicStem {
+    pub const ALL: [MusicStem; 3] = [MusicStem::Drums, MusicStem::Bass, MusicStem::Lead];
+}
+
+/// How heated the fight is, from 0 to 1
+#[derive(Resource, Default)]
+pub struct MusicIntensity(pub f32);
+
+#[derive(Debug)]
+pub enum StingerType {
+    LevelUp,
+    Death,
+}
+
+// Music and stingers, sound effects are listed in sound_effects.ron
 #[derive(AssetCollection, Resource, Default)]
 pub struct GameAudioAssets {
-    #[asset(path = "audio/player_shoot.wav")]
-    player_shoot: Handle<AudioSource>,
-    #[asset(path = "audio/player_hurt.wav")]
-    player_hurt: Handle<AudioSource>,
     #[asset(path = "audio/player_death.wav")]
     player_death: Handle<AudioSource>,
-    #[asset(path = "audio/enemy_walk.wav")]
-    enemy_walk: Handle<AudioSource>,
-    #[asset(path = "audio/enemy_hurt.wav")]
-    enemy_hurt: Handle<AudioSource>,
-    #[asset(path = "audio/enemy_death.wav")]
-    enemy_death: Handle<AudioSource>,
-    #[asset(path = "audio/xp_collect.wav")]
-    xp_collect: Handle<AudioSource>,
-    #[asset(path = "audio/ui_hover.wav")]
-    ui_hover: Handle<AudioSource>,
-    #[asset(path = "audio/ui_enter.wav")]
-    ui_press: Handle<AudioSource>,
     #[asset(path = "audio/music_game.wav")]
     music_game: Handle<AudioSource>,
+    #[asset(path = "audio/music_game_drums.wav")]
+    music_game_drums: Handle<AudioSource>,
+    #[asset(path = "audio/music_game_bass.wav")]
+    music_game_bass: Handle<AudioSource>,
+    #[asset(path = "audio/music_game_lead.wav")]
+    music_game_lead: Handle<AudioSource>,
     #[asset(path = "audio/music_menu.wav")]
     music_menu: Handle<AudioSource>,
     #[asset(path = "audio/music_upgrade.wav")]
@@ -82,18 +174,18 @@ pub struct GameAudioAssets {
 }
 
 impl GameAudioAssets {
-    pub fn get_sound_effect(&self, sound: &SoundEffectType) -> Handle<AudioSource> {
-        match sound {
-            SoundEffectType::PlayerShoot => self.player_shoot.clone(),
-            SoundEffectType::PlayerHurt => self.player_hurt.clone(),
-            SoundEffectType::PlayerDeath => self.player_death.clone(),
-            SoundEffectType::EnemyWalk => self.enemy_walk.clone(),
-            SoundEffectType::EnemyHurt => self.enemy_hurt.clone(),
-            SoundEffectType::EnemyDeath => self.enemy_death.clone(),
-            SoundEffectType::XPCollect => self.xp_collect.clone(),
-            SoundEffectType::UIHover => self.ui_hover.clone(),
-            SoundEffectType::UIEnter => self.ui_press.clone(),
-            SoundEffectType::EnterLevelUp => self.enter_level_up.clone(),
+    pub fn get_stinger(&self, stinger: &StingerType) -> Handle<AudioSource> {
+        match stinger {
+            StingerType::LevelUp => self.enter_level_up.clone(),
+            StingerType::Death => self.player_death.clone(),
+        }
+    }
+
+    pub fn get_stem(&self, stem: &MusicStem) -> Handle<AudioSource> {
+        match stem {
+            MusicStem::Drums => self.music_game_drums.clone(),
+            MusicStem::Bass => self.music_game_bass.clone(),
+            MusicStem::Lead => self.music_game_lead.clone(),
         }
     }
 
@@ -0,0 +1,93 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_asepritesheet::prelude::*;
+
+use super::{components::*, sound_effects::SoundEffects};
+use crate::{enemy::components::EnemyKilledEvent, loading::resources::SpriteSheets};
+
+pub struct SoundCuePlugin;
+
+impl Plugin for SoundCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SoundCueEvent>().add_systems(
+            Update,
+            (
+                (enemy_killed_cues, play_sound_cues).chain(),
+                play_frame_sounds.run_if(resource_exists::<SpriteSheets>()),
+            ),
+        );
+    }
+}
+
+// Gameplay events that already exist are turned into cues here instead of
+// every system that sends them also sending a cue
+fn enemy_killed_cues(
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    mut cue_event: EventWriter<SoundCueEvent>,
+) {
+    for event in killed_events.read() {
+        cue_event.send(SoundCueEvent {
+            cue: SoundCue::EnemyDied,
+            position: Some(event.location.truncate()),
+        });
+    }
+}
+
+fn play_sound_cues(
+    mut cue_events: EventReader<SoundCueEvent>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    sound_effects: Res<SoundEffects>,
+) {
+    for event in cue_events.read() {
+        let Some(sounds) = sound_effects.cues.get(&event.cue) else {
+            continue;
+        };
+        for sound in sounds {
+            sound_event.send(PlaySoundEffectEvent {
+                sound: *sound,
+                position: event.position,
+            });
+        }
+    }
+}
+
+// Plays the sounds tied to a frame once, when an animation moves onto it
+fn play_frame_sounds(
+    mut last_frames: Local<HashMap<Entity, usize>>,
+    query: Query<(
+        Entity,
+        &Handle<Spritesheet>,
+        &TextureAtlasSprite,
+        &GlobalTransform,
+    )>,
+    sprite_sheets: Res<SpriteSheets>,
+    sound_effects: Res<SoundEffects>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    let mut frames = HashMap::with_capacity(last_frames.len());
+
+    for (entity, sheet, sprite, transform) in &query {
+        frames.insert(entity, sprite.index);
+        if last_frames.get(&entity) == Some(&sprite.index) {
+            continue;
+        }
+
+        let Some(frame_sounds) = sprite_sheets
+            .path(sheet.id())
+            .and_then(|path| sound_effects.animation_frames.get(path))
+        else {
+            continue;
+        };
+
+        for frame_sound in frame_sounds {
+            if frame_sound.frame == sprite.index {
+                sound_event.send(PlaySoundEffectEvent {
+                    sound: frame_sound.sound,
+                    position: Some(transform.translation().truncate()),
+                });
+            }
+        }
+    }
+
+    // entities that are gone are dropped along the way
+    *last_frames = frames;
+}
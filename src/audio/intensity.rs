@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+
+use super::components::*;
+use crate::{
+    camera::components::WorldCamera,
+    enemy::components::Enemy,
+    game::{components::GameState, run::OnRunStart, tuning::Tuning},
+    health::components::{Dead, Health},
+    player::components::Player,
+};
+
+pub struct IntensityPlugin;
+
+impl Plugin for IntensityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnRunStart, reset_intensity).add_systems(
+            Update,
+            update_intensity.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+fn reset_intensity(mut intensity: ResMut<MusicIntensity>) {
+    intensity.0 = 0.0;
+}
+
+// Intensity is how crowded the screen is and how close the player is to dying,
+// eased towards so the music does not jump
+fn update_intensity(
+    time: Res<Time>,
+    tuning: Res<Tuning>,
+    mut intensity: ResMut<MusicIntensity>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<WorldCamera>>,
+    enemy_query: Query<&GlobalTransform, (With<Enemy>, Without<Dead>)>,
+    player_query: Query<&Health, With<Player>>,
+) {
+    let music = &tuning.music;
+
+    let on_screen = camera_query
+        .get_single()
+        .map(|(transform, projection)| {
+            let center = transform.translation().truncate();
+            let view = Rect::from_center_size(center, projection.area.size());
+            enemy_query
+                .iter()
+                .filter(|enemy| view.contains(enemy.translation().truncate()))
+                .count()
+        })
+        .unwrap_or(0);
+    let crowd = (on_screen as f32 / music.crowd_size.max(1.0)).min(1.0);
+
+    let danger = player_query
+        .get_single()
+        .map(|health| 1.0 - (health.current / health.max.max(f32::EPSILON)).clamp(0.0, 1.0))
+        .unwrap_or(0.0);
+
+    let target = (crowd * music.crowd_weight + danger * music.danger_weight).clamp(0.0, 1.0);
+
+    let speed = if target > intensity.0 {
+        music.rise_speed
+    } else {
+        music.fall_speed
+    };
+    let step = speed * time.delta_seconds();
+    intensity.0 += (target - intensity.0).clamp(-step, step);
+}
@@ -1,2 +1,6 @@
 pub mod components;
+pub mod cues;
+pub mod intensity;
+pub mod music;
+pub mod sound_effects;
 pub mod systems;
@@ -0,0 +1,325 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_kira_audio::{prelude::*, AudioSource};
+
+use super::components::*;
+use crate::{game::tuning::Tuning, settings::resources::Settings};
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<StingerChannel>()
+            .add_audio_channel::<StemChannel>()
+            .init_resource::<MusicDirector>()
+            .init_resource::<MusicIntensity>()
+            .add_event::<PlayMusicEvent>()
+            .add_event::<PlayStingerEvent>()
+            .add_systems(
+                Update,
+                (
+                    forget_stopped_music,
+                    play_music,
+                    play_stingers,
+                    unduck_music,
+                    update_music_volume,
+                    mix_stems,
+                )
+                    .chain(),
+            );
+    }
+}
+
+// music volume while a stinger plays
+const DUCK_VOLUME: f64 = 0.3;
+const DUCK_FADE: u64 = 150;
+const UNDUCK_FADE: u64 = 600;
+// stems follow intensity slowly enough to not pump with every kill
+const STEM_FADE: u64 = 800;
+
+/// Keeps track of the playing track, so switching tracks can crossfade and the
+/// game track can be resumed after the upgrade screen
+#[derive(Resource, Default)]
+pub struct MusicDirector {
+    current: Option<(MusicType, Handle<AudioInstance>)>,
+    // the game track, paused while another track plays
+    suspended: Option<Handle<AudioInstance>>,
+    // counts down while a stinger plays
+    duck: Option<Timer>,
+    // layers of the game track, paused, resumed and stopped along with it
+    stems: Vec<Stem>,
+}
+
+struct Stem {
+    layer: MusicStem,
+    instance: Handle<AudioInstance>,
+    // last volume it was set to, only changes worth hearing are sent to kira
+    volume: f64,
+}
+
+impl MusicDirector {
+    fn for_each_stem(&self, instances: &mut Assets<AudioInstance>, f: impl Fn(&mut AudioInstance)) {
+        for stem in &self.stems {
+            if let Some(instance) = instances.get_mut(&stem.instance) {
+                f(instance);
+            }
+        }
+    }
+
+    fn stop_stems(&mut self, instances: &mut Assets<AudioInstance>, tween: AudioTween) {
+        self.for_each_stem(instances, |instance| {
+            instance.stop(tween.clone());
+        });
+        self.stems.clear();
+    }
+
+    fn volume(&self, settings: &Settings) -> f64 {
+        let duck = if self.duck.is_some() {
+            DUCK_VOLUME
+        } else {
+            1.0
+        };
+        settings.audio.music_volume() * duck
+    }
+}
+
+fn fade(millis: u64) -> AudioTween {
+    AudioTween::new(Duration::from_millis(millis), AudioEasing::OutPowi(2))
+}
+
+fn stop_instance(
+    instances: &mut Assets<AudioInstance>,
+    handle: &Handle<AudioInstance>,
+    tween: AudioTween,
+) {
+    if let Some(instance) = instances.get_mut(handle) {
+        instance.stop(tween);
+    }
+}
+
+// a StopSoundEvent for the music channel already stopped every track, the
+// stems on their own channel go with them
+fn forget_stopped_music(
+    mut stop_sound_event_reader: EventReader<StopSoundEvent>,
+    mut director: ResMut<MusicDirector>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+) {
+    for event in stop_sound_event_reader.read() {
+        if event.channel == SoundChannel::Music {
+            director.current = None;
+            director.suspended = None;
+            director.stop_stems(&mut instances, fade(event.fade_out));
+        }
+    }
+}
+
+pub fn play_music(
+    mut play_music_event_reader: EventReader<PlayMusicEvent>,
+    mut director: ResMut<MusicDirector>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    stem_channel: Res<AudioChannel<StemChannel>>,
+    audio_assets: Res<GameAudioAssets>,
+    audio_sources: Res<Assets<AudioSource>>,
+) {
+    for event in play_music_event_reader.read() {
+        // resuming the track that is already playing, a new run always starts over
+        if event.resume && matches!(director.current, Some((music, _)) if music == event.sound) {
+            continue;
+        }
+
+        let tween = fade(event.crossfade);
+
+        if let Some((music, handle)) = director.current.take() {
+            if music.resumable() {
+                if let Some(instance) = instances.get_mut(&handle) {
+                    instance.pause(tween.clone());
+                }
+                director.for_each_stem(&mut instances, |instance| {
+                    instance.pause(tween.clone());
+                });
+                if let Some(old) = director.suspended.replace(handle) {
+                    stop_instance(&mut instances, &old, tween.clone());
+                }
+            } else {
+                stop_instance(&mut instances, &handle, tween.clone());
+            }
+        }
+
+        if event.resume {
+            if let Some(handle) = director.suspended.take() {
+                if let Some(instance) = instances.get_mut(&handle) {
+                    println!("Resuming music: {:?}", event.sound);
+                    instance.resume(tween.clone());
+                    director.for_each_stem(&mut instances, |instance| {
+                        instance.resume(tween.clone());
+                    });
+                    director.current = Some((event.sound, handle));
+                    continue;
+                }
+            }
+        } else if event.sound.resumable() {
+            // a new run, the track paused in the last one is not coming back
+            if let Some(old) = director.suspended.take() {
+                stop_instance(&mut instances, &old, tween.clone());
+            }
+            director.stop_stems(&mut instances, tween.clone());
+        }
+
+        let music = audio_assets.get_music(&event.sound);
+        // failed to load, play nothing instead of queueing it forever
+        if !audio_sources.contains(&music) {
+            continue;
+        }
+
+        println!("Playing music: {:?}", event.sound);
+        // the channel volume already has the settings and ducking in it
+        let mut command = music_channel.play(music);
+        command.fade_in(tween);
+        if event.looping {
+            command.looped();
+        }
+        director.current = Some((event.sound, command.handle()));
+
+        // all started in the same frame so kira keeps them in sync, silent
+        // until intensity brings them in
+        if event.sound.has_stems() {
+            for layer in MusicStem::ALL {
+                let source = audio_assets.get_stem(&layer);
+                if !audio_sources.contains(&source) {
+                    continue;
+                }
+                let mut command = stem_channel.play(source);
+                command.with_volume(0.0);
+                if event.looping {
+                    command.looped();
+                }
+                director.stems.push(Stem {
+                    layer,
+                    instance: command.handle(),
+                    volume: 0.0,
+                });
+            }
+        }
+    }
+}
+
+fn play_stingers(
+    mut play_stinger_event_reader: EventReader<PlayStingerEvent>,
+    mut director: ResMut<MusicDirector>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    stinger_channel: Res<AudioChannel<StingerChannel>>,
+    audio_assets: Res<GameAudioAssets>,
+    audio_sources: Res<Assets<AudioSource>>,
+    settings: Res<Settings>,
+) {
+    for event in play_stinger_event_reader.read() {
+        let stinger = audio_assets.get_stinger(&event.stinger);
+        let Some(source) = audio_sources.get(&stinger) else {
+            continue;
+        };
+
+        println!("Playing stinger: {:?}", event.stinger);
+        stinger_channel.play(stinger);
+
+        // the music stays down until the longest stinger is over
+        let duration = source.sound.duration().as_secs_f32();
+        let remaining = director
+            .duck
+            .as_ref()
+            .map_or(0.0, |timer| timer.remaining_secs());
+        director.duck = Some(Timer::from_seconds(
+            duration.max(remaining),
+            TimerMode::Once,
+        ));
+        music_channel
+            .set_volume(director.volume(&settings))
+            .fade_in(fade(DUCK_FADE));
+    }
+}
+
+// real time, a stinger keeps playing while the game is paused
+fn unduck_music(
+    time: Res<Time<Real>>,
+    mut director: ResMut<MusicDirector>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    settings: Res<Settings>,
+) {
+    let Some(timer) = director.duck.as_mut() else {
+        return;
+    };
+
+    if timer.tick(time.delta()).finished() {
+        director.duck = None;
+        music_channel
+            .set_volume(director.volume(&settings))
+            .fade_in(fade(UNDUCK_FADE));
+    }
+}
+
+fn update_music_volume(
+    settings: Res<Settings>,
+    director: Res<MusicDirector>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    stinger_channel: Res<AudioChannel<StingerChannel>>,
+) {
+    // The volume is only ever set on the channels, kira multiplies it into every
+    // sound played on them. Stems are the exception, see mix_stems.
+    if settings.is_changed() {
+        music_channel.set_volume(director.volume(&settings));
+        stinger_channel.set_volume(settings.audio.music_volume());
+    }
+}
+
+// intensity range over which a stem goes from silent to full volume
+fn stem_gain((from, to): (f32, f32), intensity: f32) -> f64 {
+    if to <= from {
+        return if intensity >= from { 1.0 } else { 0.0 };
+    }
+    ((intensity - from) / (to - from)).clamp(0.0, 1.0) as f64
+}
+
+// Ducking and the volume settings are part of every stem's own volume, so
+// they are picked up here as well
+fn mix_stems(
+    mut director: ResMut<MusicDirector>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    intensity: Res<MusicIntensity>,
+    tuning: Res<Tuning>,
+    settings: Res<Settings>,
+) {
+    let music_volume = director.volume(&settings);
+    for stem in &mut director.stems {
+        let range = match stem.layer {
+            MusicStem::Drums => tuning.music.drums,
+            MusicStem::Bass => tuning.music.bass,
+            MusicStem::Lead => tuning.music.lead,
+        };
+        let volume = music_volume * stem_gain(range, intensity.0);
+        if (volume - stem.volume).abs() < 0.01 {
+            continue;
+        }
+
+        if let Some(instance) = instances.get_mut(&stem.instance) {
+            instance.set_volume(volume, fade(STEM_FADE));
+            stem.volume = volume;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_fade_in_over_their_range() {
+        assert_eq!(stem_gain((0.25, 0.75), 0.0), 0.0);
+        assert_eq!(stem_gain((0.25, 0.75), 0.5), 0.5);
+        assert_eq!(stem_gain((0.25, 0.75), 1.0), 1.0);
+        // an empty range switches the stem on at once
+        assert_eq!(stem_gain((0.5, 0.5), 0.49), 0.0);
+        assert_eq!(stem_gain((0.5, 0.5), 0.5), 1.0);
+    }
+}
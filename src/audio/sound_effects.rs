@@ -0,0 +1,398 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_kira_audio::{prelude::*, AudioSource};
+use rand::Rng;
+use serde::Deserialize;
+
+use super::components::*;
+use crate::{
+    base::loader::RonAssetPlugin,
+    camera::components::WorldCamera,
+    settings::resources::{AudioSettings, Settings},
+};
+
+pub struct SoundEffectsPlugin;
+
+#[derive(Resource)]
+pub struct SoundEffectsHandle(pub Handle<SoundEffects>);
+
+impl Plugin for SoundEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<SoundEffects>::new(&["sound_effects.ron"]))
+            .init_resource::<SoundEffects>()
+            .init_resource::<SoundEffectLibrary>()
+            .init_resource::<SoundEffectVoices>()
+            .add_systems(Startup, load_sound_effects)
+            .add_systems(
+                Update,
+                (
+                    apply_sound_effects,
+                    play_sound_effect_system,
+                    update_effect_volume,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// How every sound effect plays, from `sound_effects.ron`. Saving the file
+/// while the game runs reloads the sounds it lists.
+#[derive(Asset, TypePath, Resource, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SoundEffects {
+    pub effects: HashMap<SoundEffectType, SoundEffectConfig>,
+    // sound effects each gameplay cue plays, cues left out are silent
+    pub cues: HashMap<SoundCue, Vec<SoundEffectType>>,
+    // sounds played when an animation reaches a frame, by sprite sheet path
+    pub animation_frames: HashMap<String, Vec<FrameSound>>,
+    pub spatial: SpatialConfig,
+}
+
+/// A sound effect played when a sheet's animation shows `frame`, counted from
+/// 0 like the Aseprite timeline of the whole file
+#[derive(Deserialize, Clone, Debug)]
+pub struct FrameSound {
+    pub frame: usize,
+    pub sound: SoundEffectType,
+}
+
+/// How sounds with a position are heard from the camera
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SpatialConfig {
+    // how far past the corners of the view sounds are still heard, further
+    // away they are not played
+    pub offscreen_range: f32,
+    // sounds closer than this play at full volume
+    pub full_volume_range: f32,
+    // horizontal distance at which a sound is panned all the way
+    pub pan_distance: f32,
+    // 0 keeps everything centered, 1 pans fully to one speaker
+    pub pan_strength: f32,
+}
+
+impl Default for SpatialConfig {
+    fn default() -> Self {
+        Self {
+            offscreen_range: 200.0,
+            full_volume_range: 150.0,
+            pan_distance: 500.0,
+            pan_strength: 0.8,
+        }
+    }
+}
+
+impl SpatialConfig {
+    /// Volume multiplier and kira panning (0 left, 0.5 center, 1 right) for a
+    /// sound at `source` heard from `listener` seeing `view` world units, None
+    /// when it is out of range
+    pub fn spatialize(&self, listener: Vec2, view: Vec2, source: Vec2) -> Option<(f64, f64)> {
+        // the range grows with the view, so whatever is on screen can be heard
+        let range = view.length() / 2.0 + self.offscreen_range;
+        let offset = source - listener;
+        let distance = offset.length();
+        if distance > range {
+            return None;
+        }
+
+        // fades out linearly between the full volume range and the edge
+        let falloff = (range - self.full_volume_range).max(f32::EPSILON);
+        let volume = 1.0 - ((distance - self.full_volume_range) / falloff).clamp(0.0, 1.0);
+
+        let side = (offset.x / self.pan_distance.max(f32::EPSILON)).clamp(-1.0, 1.0);
+        let panning = 0.5 + side * self.pan_strength.clamp(0.0, 1.0) * 0.5;
+
+        Some((volume as f64, panning as f64))
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SoundEffectConfig {
+    // one of these is picked at random every time the effect plays
+    pub files: Vec<String>,
+    // copies that can ring at once, more are dropped until one finishes
+    pub max_voices: usize,
+    // seconds before the effect can play again
+    pub cooldown: f64,
+    // random multipliers for playback rate and volume, picked per play
+    pub pitch: (f64, f64),
+    pub volume: (f64, f64),
+}
+
+impl Default for SoundEffectConfig {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            max_voices: 4,
+            cooldown: 0.0,
+            pitch: (1.0, 1.0),
+            volume: (1.0, 1.0),
+        }
+    }
+}
+
+/// The audio files `SoundEffects` lists, loaded from the asset server
+#[derive(Resource, Default)]
+pub struct SoundEffectLibrary {
+    variants: HashMap<SoundEffectType, Vec<Handle<AudioSource>>>,
+}
+
+impl SoundEffectLibrary {
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<AudioSource>> {
+        self.variants.values().flatten()
+    }
+
+    fn pick(&self, sound: SoundEffectType) -> Option<Handle<AudioSource>> {
+        let variants = self.variants.get(&sound)?;
+        if variants.is_empty() {
+            return None;
+        }
+        let index = rand::thread_rng().gen_range(0..variants.len());
+        Some(variants[index].clone())
+    }
+}
+
+// Voices still playing and when each effect last started
+#[derive(Resource, Default)]
+struct SoundEffectVoices {
+    playing: HashMap<SoundEffectType, Vec<Voice>>,
+    last_played: HashMap<SoundEffectType, f64>,
+}
+
+struct Voice {
+    instance: Handle<AudioInstance>,
+    // distance and random volume, the volume setting is multiplied on top
+    volume: f64,
+}
+
+fn setting_volume(audio: &AudioSettings, sound: SoundEffectType) -> f64 {
+    match sound.channel() {
+        SoundChannel::Ui => audio.ui_volume(),
+        _ => audio.sfx_volume(),
+    }
+}
+
+fn load_sound_effects(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SoundEffectsHandle(asset_server.load("sound_effects.ron")));
+}
+
+pub fn apply_sound_effects(
+    mut asset_events: EventReader<AssetEvent<SoundEffects>>,
+    loaded: Res<Assets<SoundEffects>>,
+    handle: Res<SoundEffectsHandle>,
+    asset_server: Res<AssetServer>,
+    mut sound_effects: ResMut<SoundEffects>,
+    mut library: ResMut<SoundEffectLibrary>,
+) {
+    for event in asset_events.read() {
+        if event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0) {
+            if let Some(loaded) = loaded.get(&handle.0) {
+                println!("Applying sound_effects.ron");
+                *sound_effects = loaded.clone();
+                library.variants = loaded
+                    .effects
+                    .iter()
+                    .map(|(sound, config)| {
+                        let files = config.files.iter().map(|file| asset_server.load(file));
+                        (*sound, files.collect())
+                    })
+                    .collect();
+            }
+        }
+    }
+}
+
+// a random value between the two ends of the range, in either order
+fn vary((from, to): (f64, f64)) -> f64 {
+    if from == to {
+        return from;
+    }
+    rand::thread_rng().gen_range(from.min(to)..=from.max(to))
+}
+
+fn play_sound_effect_system(
+    mut play_sound_event_reader: EventReader<PlaySoundEffectEvent>,
+    mut voices: ResMut<SoundEffectVoices>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+    ui_channel: Res<AudioChannel<UiChannel>>,
+    sound_effects: Res<SoundEffects>,
+    library: Res<SoundEffectLibrary>,
+    audio_sources: Res<Assets<AudioSource>>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<WorldCamera>>,
+) {
+    let now = time.elapsed_seconds_f64();
+    let (listener, view) = camera_query
+        .get_single()
+        .map(|(transform, projection)| (transform.translation().truncate(), projection.area.size()))
+        .unwrap_or((Vec2::ZERO, Vec2::ZERO));
+
+    for event in play_sound_event_reader.read() {
+        let Some(config) = sound_effects.effects.get(&event.sound) else {
+            continue;
+        };
+
+        // too far away to hear, culled before it takes a voice or the cooldown
+        let (distance_volume, panning) = match event.position {
+            Some(position) => match sound_effects.spatial.spatialize(listener, view, position) {
+                Some(spatial) => spatial,
+                None => continue,
+            },
+            None => (1.0, 0.5),
+        };
+
+        // several hits in one frame count as one
+        if voices
+            .last_played
+            .get(&event.sound)
+            .is_some_and(|last| now - last < config.cooldown)
+        {
+            continue;
+        }
+
+        let playing = voices.playing.entry(event.sound).or_default();
+        playing.retain(|voice| {
+            let state = match event.sound.channel() {
+                SoundChannel::Ui => ui_channel.state(&voice.instance),
+                _ => sfx_channel.state(&voice.instance),
+            };
+            !matches!(state, PlaybackState::Stopped)
+        });
+        if playing.len() >= config.max_voices {
+            continue;
+        }
+
+        let Some(sound) = library.pick(event.sound) else {
+            continue;
+        };
+        if !audio_sources.contains(&sound) {
+            continue;
+        }
+
+        println!("Playing sound: {:?}", event.sound);
+        let rate = vary(config.pitch);
+        let volume = vary(config.volume) * distance_volume;
+        let channel_volume = setting_volume(&settings.audio, event.sound) * volume;
+        let instance = match event.sound.channel() {
+            SoundChannel::Ui => ui_channel
+                .play(sound)
+                .with_volume(channel_volume)
+                .with_playback_rate(rate)
+                .with_panning(panning)
+                .handle(),
+            _ => sfx_channel
+                .play(sound)
+                .with_volume(channel_volume)
+                .with_playback_rate(rate)
+                .with_panning(panning)
+                .handle(),
+        };
+
+        playing.push(Voice { instance, volume });
+        voices.last_played.insert(event.sound, now);
+    }
+}
+
+// Carries a volume setting change over to the effects already playing, each
+// keeps its own distance and random volume
+fn update_effect_volume(
+    settings: Res<Settings>,
+    mut previous: Local<Option<AudioSettings>>,
+    voices: Res<SoundEffectVoices>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    if previous
+        .as_ref()
+        .is_some_and(|previous| *previous != settings.audio)
+    {
+        for (sound, playing) in &voices.playing {
+            let setting = setting_volume(&settings.audio, *sound);
+            for voice in playing {
+                if let Some(instance) = audio_instances.get_mut(&voice.instance) {
+                    instance.set_volume(setting * voice.volume, AudioTween::default());
+                }
+            }
+        }
+    }
+    *previous = Some(settings.audio.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_sound_effects_parse() {
+        let effects: SoundEffects =
+            ron::from_str(include_str!("../../assets/sound_effects.ron")).unwrap();
+
+        assert!(effects
+            .effects
+            .values()
+            .all(|config| !config.files.is_empty() && config.max_voices > 0));
+        // every cue and animation frame plays an effect the table configures
+        assert!(effects
+            .cues
+            .values()
+            .flatten()
+            .chain(
+                effects
+                    .animation_frames
+                    .values()
+                    .flatten()
+                    .map(|frame| &frame.sound)
+            )
+            .all(|sound| effects.effects.contains_key(sound)));
+    }
+
+    #[test]
+    fn spatialize_fades_pans_and_culls() {
+        let spatial = SpatialConfig {
+            offscreen_range: 150.0,
+            full_volume_range: 100.0,
+            pan_distance: 200.0,
+            pan_strength: 1.0,
+        };
+        // 500 units to the corners, heard up to 650 away
+        let view = Vec2::new(600.0, 800.0);
+
+        assert_eq!(
+            spatial.spatialize(Vec2::ZERO, view, Vec2::ZERO),
+            Some((1.0, 0.5))
+        );
+        assert_eq!(
+            spatial.spatialize(Vec2::ZERO, view, Vec2::new(375.0, 0.0)),
+            Some((0.5, 1.0))
+        );
+        assert_eq!(
+            spatial.spatialize(Vec2::new(100.0, 0.0), view, Vec2::new(0.0, 0.0)),
+            Some((1.0, 0.25))
+        );
+        // the corners of the view are still heard
+        assert!(spatial
+            .spatialize(Vec2::ZERO, view, Vec2::new(300.0, 400.0))
+            .is_some_and(|(volume, _)| volume > 0.0));
+        assert_eq!(
+            spatial.spatialize(Vec2::ZERO, view, Vec2::new(0.0, 651.0)),
+            None
+        );
+        // zooming out widens the range
+        assert!(spatial
+            .spatialize(Vec2::ZERO, view * 1.5, Vec2::new(0.0, 651.0))
+            .is_some());
+    }
+
+    #[test]
+    fn vary_stays_in_range() {
+        assert_eq!(vary((1.0, 1.0)), 1.0);
+        for _ in 0..100 {
+            let value = vary((1.1, 0.9));
+            assert!((0.9..=1.1).contains(&value));
+        }
+    }
+}
@@ -1,66 +1,51 @@
 use std::time::Duration;
 
-use crate::game::components::GameState;
-
-use super::components::*;
+use super::{
+    components::*,
+    cues::SoundCuePlugin,
+    intensity::IntensityPlugin,
+    music::{play_music, MusicPlugin},
+    sound_effects::SoundEffectsPlugin,
+};
 use bevy::prelude::*;
-use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::prelude::*;
 
 pub struct AjmAudioPlugin;
 
 impl Plugin for AjmAudioPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugins((
+            MusicPlugin,
+            IntensityPlugin,
+            SoundEffectsPlugin,
+            SoundCuePlugin,
+        ));
+        app.add_audio_channel::<SfxChannel>();
+        app.add_audio_channel::<UiChannel>();
         app.add_event::<PlaySoundEffectEvent>();
-        app.add_event::<PlayMusicEvent>();
         app.add_event::<StopSoundEvent>();
-        app.add_loading_state(
-            LoadingState::new(GameState::Loading)
-                .continue_to_state(GameState::Menu)
-                .load_collection::<GameAudioAssets>(),
-        );
         app.init_resource::<GameAudioAssets>();
-        app.add_systems(Update, play_music);
-        app.add_systems(Update, play_sound_effect_system);
-        app.add_systems(Update, stop_sound_system);
+        // stopping first, so a track started in the same frame keeps playing
+        app.add_systems(Update, stop_sound_system.before(play_music));
     }
 }
 
-fn play_music(
-    mut play_sound_event_reader: EventReader<PlayMusicEvent>,
-    audio: Res<Audio>,
-    audio_assets: Res<GameAudioAssets>,
-) {
-    for event in play_sound_event_reader.read() {
-        println!("Playing music: {:?}", event.sound);
-        if event.looping {
-            audio
-                .play(audio_assets.get_music(&event.sound))
-                .fade_in(AudioTween::new(
-                    Duration::from_millis(event.fade_in),
-                    AudioEasing::OutPowi(2),
-                ))
-                .looped();
-            return;
-        } else {
-            audio.play(audio_assets.get_music(&event.sound));
-        }
-    }
+fn fade(millis: u64) -> AudioTween {
+    AudioTween::new(Duration::from_millis(millis), AudioEasing::OutPowi(2))
 }
 
-fn play_sound_effect_system(
-    mut play_sound_event_reader: EventReader<PlaySoundEffectEvent>,
-    audio: Res<Audio>,
-    audio_assets: Res<GameAudioAssets>,
+fn stop_sound_system(
+    mut stop_sound_event_reader: EventReader<StopSoundEvent>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+    ui_channel: Res<AudioChannel<UiChannel>>,
 ) {
-    for event in play_sound_event_reader.read() {
-        println!("Playing sound: {:?}", event.sound);
-        audio.play(audio_assets.get_sound_effect(&event.sound));
-    }
-}
-
-fn stop_sound_system(mut stop_sound_event_reader: EventReader<StopSoundEvent>, audio: Res<Audio>) {
-    for _ in stop_sound_event_reader.read() {
-        audio.stop();
+    for event in stop_sound_event_reader.read() {
+        let mut command = match event.channel {
+            SoundChannel::Music => music_channel.stop(),
+            SoundChannel::Sfx => sfx_channel.stop(),
+            SoundChannel::Ui => ui_channel.stop(),
+        };
+        command.fade_out(fade(event.fade_out));
     }
 }
@@ -0,0 +1,727 @@
+use std::io::Read;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    sprite::Anchor,
+    utils::BoxedFuture,
+};
+use bevy_asepritesheet::prelude::*;
+use flate2::read::ZlibDecoder;
+use thiserror::Error;
+
+/// Loads `.aseprite` source files straight into the `Spritesheet` asset the
+/// exported `.sprite.json` files produce, so source files hot reload on save
+pub struct AsepriteLoaderPlugin;
+
+impl Plugin for AsepriteLoaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_asset_loader(AsepriteLoader);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AsepriteError {
+    #[error("could not read file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not an aseprite file")]
+    InvalidMagic,
+    #[error("file ends in the middle of a chunk")]
+    UnexpectedEnd,
+    #[error("unsupported color depth {0}")]
+    UnsupportedColorDepth(u16),
+    #[error("{0}x{1} is larger than a sprite can be")]
+    TooLarge(u32, u32),
+}
+
+const HEADER_MAGIC: u16 = 0xA5E0;
+const HEADER_SIZE: usize = 128;
+const FRAME_MAGIC: u16 = 0xF1FA;
+const FRAME_HEADER_SIZE: usize = 16;
+const CHUNK_HEADER_SIZE: usize = 6;
+
+// Sizes and counts in the file are only trusted this far before allocating,
+// a corrupt header must not reserve gigabytes
+const MAX_CANVAS_SIZE: u32 = 4096;
+const MAX_PREALLOCATION: usize = 1 << 20;
+
+const CHUNK_OLD_PALETTE: u16 = 0x0004;
+const CHUNK_LAYER: u16 = 0x2004;
+const CHUNK_CEL: u16 = 0x2005;
+const CHUNK_TAGS: u16 = 0x2018;
+const CHUNK_PALETTE: u16 = 0x2019;
+
+const LAYER_VISIBLE: u16 = 1;
+const LAYER_TYPE_NORMAL: u16 = 0;
+
+const CEL_RAW: u16 = 0;
+const CEL_LINKED: u16 = 1;
+const CEL_COMPRESSED: u16 = 2;
+
+/// A parsed .aseprite file with every frame flattened to RGBA pixels
+pub struct Aseprite {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<AsepriteFrame>,
+    pub tags: Vec<AsepriteTag>,
+}
+
+pub struct AsepriteFrame {
+    // milliseconds
+    pub duration: u16,
+    // width * height RGBA pixels, all visible layers blended together
+    pub pixels: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagDirection {
+    Forward,
+    Reverse,
+    PingPong,
+    PingPongReverse,
+}
+
+pub struct AsepriteTag {
+    pub name: String,
+    pub from: usize,
+    pub to: usize,
+    pub direction: TagDirection,
+}
+
+impl AsepriteTag {
+    /// Frame indices in play order
+    pub fn frame_indices(&self) -> Vec<usize> {
+        let forward: Vec<usize> = (self.from..=self.to).collect();
+        let reverse: Vec<usize> = forward.iter().rev().copied().collect();
+
+        // ping pong does not repeat the frames it turns around on
+        let bounce = |there: &[usize], back: &[usize]| {
+            let mut frames = there.to_vec();
+            if back.len() > 2 {
+                frames.extend(&back[1..back.len() - 1]);
+            }
+            frames
+        };
+
+        match self.direction {
+            TagDirection::Forward => forward,
+            TagDirection::Reverse => reverse,
+            TagDirection::PingPong => bounce(&forward, &reverse),
+            TagDirection::PingPongReverse => bounce(&reverse, &forward),
+        }
+    }
+}
+
+struct Layer {
+    visible: bool,
+    opacity: u8,
+}
+
+// little endian cursor over the file, every read fails instead of panicking on
+// a truncated file
+struct Bytes<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Bytes<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], AsepriteError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .ok_or(AsepriteError::UnexpectedEnd)?;
+        let bytes = self
+            .data
+            .get(self.pos..end)
+            .ok_or(AsepriteError::UnexpectedEnd)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), AsepriteError> {
+        self.take(len).map(|_| ())
+    }
+
+    fn byte(&mut self) -> Result<u8, AsepriteError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn word(&mut self) -> Result<u16, AsepriteError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn short(&mut self) -> Result<i16, AsepriteError> {
+        Ok(self.word()? as i16)
+    }
+
+    fn dword(&mut self) -> Result<u32, AsepriteError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, AsepriteError> {
+        let len = self.word()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+}
+
+// End of a frame or chunk, which must hold at least its own header and stay
+// inside what contains it. Anything else would re-read or skip past the data.
+fn section_end(
+    start: usize,
+    size: usize,
+    min: usize,
+    limit: usize,
+) -> Result<usize, AsepriteError> {
+    let end = start
+        .checked_add(size)
+        .ok_or(AsepriteError::UnexpectedEnd)?;
+    if size < min || end > limit {
+        return Err(AsepriteError::UnexpectedEnd);
+    }
+    Ok(end)
+}
+
+/// Parses an .aseprite file, see
+/// https://github.com/aseprite/aseprite/blob/main/docs/ase-file-specs.md
+pub fn parse_aseprite(data: &[u8]) -> Result<Aseprite, AsepriteError> {
+    let mut header = Bytes::new(data);
+    header.skip(4)?;
+    if header.word()? != HEADER_MAGIC {
+        return Err(AsepriteError::InvalidMagic);
+    }
+    let frame_count = header.word()? as usize;
+    let width = header.word()? as u32;
+    let height = header.word()? as u32;
+    let color_depth = header.word()?;
+    let layer_opacity_valid = header.dword()? & 1 != 0;
+    header.skip(10)?;
+    let transparent_index = header.byte()?;
+    if width > MAX_CANVAS_SIZE || height > MAX_CANVAS_SIZE {
+        return Err(AsepriteError::TooLarge(width, height));
+    }
+
+    let bytes_per_pixel = match color_depth {
+        32 => 4,
+        16 => 2,
+        8 => 1,
+        depth => return Err(AsepriteError::UnsupportedColorDepth(depth)),
+    };
+
+    let mut layers = Vec::new();
+    // visibility of the group at each child level, a hidden group hides its children
+    let mut group_visible: Vec<bool> = Vec::new();
+    let mut palette = vec![[0u8; 4]; 256];
+    let mut tags = Vec::new();
+    // no more frames than the file has room for
+    let capacity = frame_count.min(data.len().saturating_sub(HEADER_SIZE) / FRAME_HEADER_SIZE);
+    let mut frames: Vec<AsepriteFrame> = Vec::with_capacity(capacity);
+    // cels of every frame, kept so linked cels can reuse them
+    let mut frame_cels: Vec<Vec<Cel>> = Vec::with_capacity(capacity);
+
+    let mut file = Bytes::new(data);
+    file.skip(HEADER_SIZE)?;
+    for _ in 0..frame_count {
+        let frame_start = file.pos;
+        let frame_size = file.dword()? as usize;
+        let frame_end = section_end(frame_start, frame_size, FRAME_HEADER_SIZE, data.len())?;
+        if file.word()? != FRAME_MAGIC {
+            return Err(AsepriteError::InvalidMagic);
+        }
+        let old_chunk_count = file.word()? as usize;
+        let duration = file.word()?;
+        file.skip(2)?;
+        let chunk_count = match file.dword()? {
+            0 => old_chunk_count,
+            count => count as usize,
+        };
+
+        let mut cels = Vec::new();
+        for _ in 0..chunk_count {
+            let chunk_start = file.pos;
+            let chunk_size = file.dword()? as usize;
+            let chunk_end = section_end(chunk_start, chunk_size, CHUNK_HEADER_SIZE, frame_end)?;
+            let chunk_type = file.word()?;
+            let mut chunk = Bytes::new(file.take(chunk_size - CHUNK_HEADER_SIZE)?);
+
+            match chunk_type {
+                CHUNK_LAYER => {
+                    let flags = chunk.word()?;
+                    let layer_type = chunk.word()?;
+                    let child_level = chunk.word()? as usize;
+                    chunk.skip(6)?;
+                    let opacity = chunk.byte()?;
+
+                    group_visible.truncate(child_level);
+                    let visible =
+                        flags & LAYER_VISIBLE != 0 && group_visible.iter().all(|visible| *visible);
+                    group_visible.push(visible);
+
+                    layers.push(Layer {
+                        // groups and tilemaps are not drawn themselves
+                        visible: visible && layer_type == LAYER_TYPE_NORMAL,
+                        opacity: if layer_opacity_valid { opacity } else { 255 },
+                    });
+                }
+                CHUNK_CEL => cels.push(parse_cel(&mut chunk, &frame_cels, bytes_per_pixel)?),
+                CHUNK_TAGS => {
+                    let count = chunk.word()?;
+                    chunk.skip(8)?;
+                    for _ in 0..count {
+                        let from = chunk.word()? as usize;
+                        let to = chunk.word()? as usize;
+                        let direction = match chunk.byte()? {
+                            1 => TagDirection::Reverse,
+                            2 => TagDirection::PingPong,
+                            3 => TagDirection::PingPongReverse,
+                            _ => TagDirection::Forward,
+                        };
+                        chunk.skip(12)?;
+                        let name = chunk.string()?;
+                        tags.push(AsepriteTag {
+                            name,
+                            from,
+                            to,
+                            direction,
+                        });
+                    }
+                }
+                CHUNK_PALETTE => {
+                    chunk.skip(4)?;
+                    let first = chunk.dword()? as usize;
+                    let last = chunk.dword()? as usize;
+                    chunk.skip(8)?;
+                    for index in first..=last {
+                        let flags = chunk.word()?;
+                        let color = [chunk.byte()?, chunk.byte()?, chunk.byte()?, chunk.byte()?];
+                        if flags & 1 != 0 {
+                            chunk.string()?;
+                        }
+                        if let Some(entry) = palette.get_mut(index) {
+                            *entry = color;
+                        }
+                    }
+                }
+                CHUNK_OLD_PALETTE => {
+                    let packets = chunk.word()?;
+                    let mut index = 0;
+                    for _ in 0..packets {
+                        index += chunk.byte()? as usize;
+                        let count = match chunk.byte()? {
+                            0 => 256,
+                            count => count as usize,
+                        };
+                        for _ in 0..count {
+                            let color = [chunk.byte()?, chunk.byte()?, chunk.byte()?, 255];
+                            if let Some(entry) = palette.get_mut(index) {
+                                // the new palette chunk wins when both are present
+                                if entry[3] == 0 {
+                                    *entry = color;
+                                }
+                            }
+                            index += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            file.pos = chunk_end;
+        }
+
+        let pixels = flatten(
+            &cels,
+            &layers,
+            width,
+            height,
+            bytes_per_pixel,
+            &palette,
+            transparent_index,
+        );
+        frames.push(AsepriteFrame { duration, pixels });
+        frame_cels.push(cels);
+
+        file.pos = frame_end;
+    }
+
+    Ok(Aseprite {
+        width,
+        height,
+        frames,
+        tags,
+    })
+}
+
+#[derive(Clone)]
+struct Cel {
+    layer: usize,
+    x: i32,
+    y: i32,
+    opacity: u8,
+    width: u32,
+    height: u32,
+    // pixels in the file's color depth
+    data: Vec<u8>,
+}
+
+fn parse_cel(
+    chunk: &mut Bytes,
+    frame_cels: &[Vec<Cel>],
+    bytes_per_pixel: usize,
+) -> Result<Cel, AsepriteError> {
+    let layer = chunk.word()? as usize;
+    let x = chunk.short()? as i32;
+    let y = chunk.short()? as i32;
+    let opacity = chunk.byte()?;
+    let cel_type = chunk.word()?;
+    chunk.skip(7)?;
+
+    let empty = Cel {
+        layer,
+        x,
+        y,
+        opacity,
+        width: 0,
+        height: 0,
+        data: Vec::new(),
+    };
+
+    match cel_type {
+        CEL_RAW | CEL_COMPRESSED => {
+            let width = chunk.word()? as u32;
+            let height = chunk.word()? as u32;
+            let len = width as usize * height as usize * bytes_per_pixel;
+
+            let data = if cel_type == CEL_RAW {
+                chunk.take(len)?.to_vec()
+            } else {
+                // never inflates past the size the cel claims
+                let mut data = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+                ZlibDecoder::new(chunk.take(chunk.data.len() - chunk.pos)?)
+                    .take(len as u64)
+                    .read_to_end(&mut data)?;
+                data
+            };
+            if data.len() < len {
+                return Err(AsepriteError::UnexpectedEnd);
+            }
+
+            Ok(Cel {
+                width,
+                height,
+                data,
+                ..empty
+            })
+        }
+        CEL_LINKED => {
+            let frame = chunk.word()? as usize;
+            let linked = frame_cels
+                .get(frame)
+                .and_then(|cels| cels.iter().find(|cel| cel.layer == layer));
+
+            Ok(match linked {
+                Some(cel) => Cel {
+                    x,
+                    y,
+                    opacity,
+                    ..cel.clone()
+                },
+                None => empty,
+            })
+        }
+        // tilemap cels are skipped like tilemap layers
+        _ => Ok(empty),
+    }
+}
+
+// Blends the cels of one frame bottom layer first, with the normal blend mode
+fn flatten(
+    cels: &[Cel],
+    layers: &[Layer],
+    width: u32,
+    height: u32,
+    bytes_per_pixel: usize,
+    palette: &[[u8; 4]],
+    transparent_index: u8,
+) -> Vec<u8> {
+    let mut pixels = vec![0u8; width as usize * height as usize * 4];
+
+    let mut cels: Vec<&Cel> = cels.iter().collect();
+    cels.sort_by_key(|cel| cel.layer);
+
+    for cel in cels {
+        let Some(layer) = layers.get(cel.layer).filter(|layer| layer.visible) else {
+            continue;
+        };
+        let opacity = cel.opacity as u32 * layer.opacity as u32 / 255;
+
+        for cel_y in 0..cel.height {
+            for cel_x in 0..cel.width {
+                let x = cel.x + cel_x as i32;
+                let y = cel.y + cel_y as i32;
+                if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+                    continue;
+                }
+
+                let source =
+                    (cel_y as usize * cel.width as usize + cel_x as usize) * bytes_per_pixel;
+                let source = &cel.data[source..source + bytes_per_pixel];
+                let [r, g, b, a] = match bytes_per_pixel {
+                    4 => [source[0], source[1], source[2], source[3]],
+                    2 => [source[0], source[0], source[0], source[1]],
+                    _ if source[0] == transparent_index => [0, 0, 0, 0],
+                    _ => palette[source[0] as usize],
+                };
+
+                let alpha = a as u32 * opacity / 255;
+                if alpha == 0 {
+                    continue;
+                }
+
+                let target = ((y as u32 * width + x as u32) * 4) as usize;
+                let target = &mut pixels[target..target + 4];
+                let below = target[3] as u32;
+                let out_alpha = alpha + below * (255 - alpha) / 255;
+                for (channel, value) in [r, g, b].into_iter().enumerate() {
+                    let over = value as u32 * alpha;
+                    let under = target[channel] as u32 * below * (255 - alpha) / 255;
+                    target[channel] = ((over + under) / out_alpha) as u8;
+                }
+                target[3] = out_alpha as u8;
+            }
+        }
+    }
+
+    pixels
+}
+
+struct AsepriteLoader;
+
+impl AssetLoader for AsepriteLoader {
+    type Asset = Spritesheet;
+    type Settings = ();
+    type Error = AsepriteError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Spritesheet, AsepriteError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let aseprite = parse_aseprite(&bytes)?;
+
+            // frames side by side with a pixel of padding, like the json exports
+            let frame_count = aseprite.frames.len() as u32;
+            let sheet_width = (aseprite.width + 1) * frame_count.max(1) - 1;
+            let mut data = vec![0u8; (sheet_width * aseprite.height * 4) as usize];
+            for (index, frame) in aseprite.frames.iter().enumerate() {
+                let left = index as u32 * (aseprite.width + 1);
+                for y in 0..aseprite.height {
+                    let row = (y * aseprite.width * 4) as usize;
+                    let target = ((y * sheet_width + left) * 4) as usize;
+                    let len = (aseprite.width * 4) as usize;
+                    data[target..target + len].copy_from_slice(&frame.pixels[row..row + len]);
+                }
+            }
+
+            let image = Image::new(
+                Extent3d {
+                    width: sheet_width,
+                    height: aseprite.height,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                data,
+                TextureFormat::Rgba8UnormSrgb,
+            );
+            let image_handle = load_context.add_labeled_asset("image".to_string(), image);
+
+            let mut atlas = TextureAtlas::new_empty(
+                image_handle.clone(),
+                Vec2::new(sheet_width as f32, aseprite.height as f32),
+            );
+            let frames = (0..frame_count)
+                .map(|index| {
+                    let left = (index * (aseprite.width + 1)) as f32;
+                    let rect = Rect::new(
+                        left,
+                        0.0,
+                        left + aseprite.width as f32,
+                        aseprite.height as f32,
+                    );
+                    atlas.add_texture(rect);
+                    Frame::new(rect, Anchor::Center)
+                })
+                .collect();
+            let atlas_handle = load_context.add_labeled_asset("atlas".to_string(), atlas);
+
+            let anims = aseprite
+                .tags
+                .iter()
+                .map(|tag| {
+                    let anim_frames = tag
+                        .frame_indices()
+                        .into_iter()
+                        .filter_map(|index| {
+                            let frame = aseprite.frames.get(index)?;
+                            Some(AnimFrame::new(index, frame.duration as f32 / 1000.0))
+                        })
+                        .collect();
+                    Anim::new(tag.name.clone(), anim_frames, AnimEndAction::Loop)
+                })
+                .collect();
+
+            Ok(Spritesheet::new(frames, anims, image_handle, atlas_handle))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite", "ase"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the export of enemy_3 is up to date, so both must agree
+    #[test]
+    fn parses_tags_and_frames_like_the_export() {
+        let aseprite = parse_aseprite(include_bytes!("../../assets/enemy_3.aseprite")).unwrap();
+        let export: serde_json::Value =
+            serde_json::from_str(include_str!("../../assets/enemy_3.sprite.json")).unwrap();
+        assert_eq!((aseprite.width, aseprite.height), (8, 8));
+
+        let durations: Vec<u64> = aseprite
+            .frames
+            .iter()
+            .map(|frame| frame.duration as u64)
+            .collect();
+        let exported_durations: Vec<u64> = export["frames"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|frame| frame["duration"].as_u64().unwrap())
+            .collect();
+        assert_eq!(durations, exported_durations);
+
+        let tags: Vec<(&str, u64, u64)> = aseprite
+            .tags
+            .iter()
+            .map(|tag| (tag.name.as_str(), tag.from as u64, tag.to as u64))
+            .collect();
+        let exported_tags: Vec<(&str, u64, u64)> = export["meta"]["frameTags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tag| {
+                (
+                    tag["name"].as_str().unwrap(),
+                    tag["from"].as_u64().unwrap(),
+                    tag["to"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(tags, exported_tags);
+
+        // every frame has something drawn on it
+        assert!(aseprite
+            .frames
+            .iter()
+            .all(|frame| frame.pixels.chunks(4).any(|pixel| pixel[3] > 0)));
+    }
+
+    // a 1x1 file with a single frame holding the given (size, type) chunks,
+    // each padded out to the size it claims
+    fn file_with_frame(frame_size: Option<u32>, chunks: &[(u32, u16)]) -> Vec<u8> {
+        let mut header = vec![0u8; HEADER_SIZE];
+        header[4..6].copy_from_slice(&HEADER_MAGIC.to_le_bytes());
+        header[6..8].copy_from_slice(&1u16.to_le_bytes());
+        header[8..10].copy_from_slice(&1u16.to_le_bytes());
+        header[10..12].copy_from_slice(&1u16.to_le_bytes());
+        header[12..14].copy_from_slice(&32u16.to_le_bytes());
+
+        let mut body = Vec::new();
+        for (size, chunk_type) in chunks {
+            body.extend(size.to_le_bytes());
+            body.extend(chunk_type.to_le_bytes());
+            body.resize(
+                body.len() + (*size as usize).saturating_sub(CHUNK_HEADER_SIZE),
+                0,
+            );
+        }
+
+        let frame_size = frame_size.unwrap_or((FRAME_HEADER_SIZE + body.len()) as u32);
+        let mut frame = Vec::new();
+        frame.extend(frame_size.to_le_bytes());
+        frame.extend(FRAME_MAGIC.to_le_bytes());
+        frame.extend((chunks.len() as u16).to_le_bytes());
+        frame.extend(100u16.to_le_bytes());
+        frame.extend([0, 0]);
+        frame.extend((chunks.len() as u32).to_le_bytes());
+
+        [header, frame, body].concat()
+    }
+
+    fn unexpected_end(data: &[u8]) -> bool {
+        matches!(parse_aseprite(data), Err(AsepriteError::UnexpectedEnd))
+    }
+
+    #[test]
+    fn parses_a_well_formed_frame() {
+        let aseprite = parse_aseprite(&file_with_frame(None, &[(12, 0x2007)])).unwrap();
+        assert_eq!(aseprite.frames.len(), 1);
+        assert_eq!(aseprite.frames[0].duration, 100);
+    }
+
+    #[test]
+    fn rejects_a_frame_smaller_than_its_header() {
+        assert!(unexpected_end(&file_with_frame(Some(0), &[])));
+        assert!(unexpected_end(&file_with_frame(Some(8), &[])));
+    }
+
+    #[test]
+    fn rejects_a_frame_past_the_end_of_the_file() {
+        assert!(unexpected_end(&file_with_frame(Some(1000), &[])));
+        assert!(unexpected_end(&file_with_frame(Some(u32::MAX), &[])));
+    }
+
+    #[test]
+    fn rejects_a_chunk_smaller_than_its_header() {
+        assert!(unexpected_end(&file_with_frame(None, &[(4, 0x2007)])));
+        assert!(unexpected_end(&file_with_frame(None, &[(0, 0x2007)])));
+    }
+
+    #[test]
+    fn rejects_a_chunk_past_the_end_of_its_frame() {
+        // the chunk fits in the file but not in the frame it belongs to
+        let mut data = file_with_frame(Some(FRAME_HEADER_SIZE as u32 + 6), &[(12, 0x2007)]);
+        data.extend([0; 16]);
+        assert!(unexpected_end(&data));
+
+        let mut data = file_with_frame(None, &[(12, 0x2007)]);
+        let chunk_start = HEADER_SIZE + FRAME_HEADER_SIZE;
+        data[chunk_start..chunk_start + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(unexpected_end(&data));
+    }
+
+    #[test]
+    fn ping_pong_does_not_repeat_the_ends() {
+        let tag = AsepriteTag {
+            name: "walk".to_string(),
+            from: 2,
+            to: 5,
+            direction: TagDirection::PingPong,
+        };
+        assert_eq!(tag.frame_indices(), [2, 3, 4, 5, 4, 3]);
+    }
+}
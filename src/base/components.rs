@@ -1,13 +1,5 @@
 use bevy::prelude::*;
 
-pub const WINDOW_PADDING: f32 = 50.0;
-
-#[derive(Component)]
-pub struct AnimationIndices {
-    pub first: usize,
-    pub last: usize,
-}
-
 #[derive(Component, Deref, DerefMut)]
 pub struct AnimationTimer(pub Timer);
 
@@ -0,0 +1,76 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Registers an asset type that is deserialized from a RON file
+pub struct RonAssetPlugin<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<A>,
+}
+
+impl<A> RonAssetPlugin<A> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A> Plugin for RonAssetPlugin<A>
+where
+    A: Asset + for<'de> Deserialize<'de>,
+{
+    fn build(&self, app: &mut App) {
+        app.init_asset::<A>()
+            .register_asset_loader(RonAssetLoader::<A> {
+                extensions: self.extensions,
+                _marker: PhantomData,
+            });
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum RonLoaderError {
+    #[error("could not read file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse RON: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+struct RonAssetLoader<A> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<A>,
+}
+
+impl<A> AssetLoader for RonAssetLoader<A>
+where
+    A: Asset + for<'de> Deserialize<'de>,
+{
+    type Asset = A;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<A, RonLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            Ok(ron::de::from_bytes::<A>(&bytes)?)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
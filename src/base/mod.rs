@@ -1,2 +1,3 @@
+pub mod aseprite;
 pub mod components;
-pub mod resources;
+pub mod loader;
@@ -1,11 +1,29 @@
 use bevy::prelude::*;
 
+/// What the camera follows. `aim` is where the target is looking, the camera
+/// leans that way together with the direction it moves in.
+#[derive(Component, Default)]
+pub struct Target {
+    pub aim: Vec2,
+}
+
+/// The camera looking at the world, drawing into the pixel canvas
 #[derive(Component)]
-pub struct Target;
+pub struct WorldCamera;
 
 #[derive(Component)]
 pub struct CameraFollow {
     pub acceleration: f32,
     pub smoothness: f32,
     pub target_position: Vec3,
+    // how far ahead of the target the camera leads
+    pub look_ahead: f32,
+    // zoom with no enemies around and with `zoom_enemies` of them, 1.0 shows
+    // the virtual resolution at one art pixel per virtual pixel. The canvas
+    // only steps out as far as whole pixels allow, so it snaps between levels.
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub zoom_enemies: f32,
+    // share of the way to the wanted zoom covered per second
+    pub zoom_speed: f32,
 }
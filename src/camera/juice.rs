@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+
+use super::{components::WorldCamera, systems::camera_follow_system};
+use crate::{
+    enemy::components::{EnemyKilledEvent, EnemyKind},
+    game::tuning::Tuning,
+    hurt::components::Hurting,
+    player::components::Player,
+    settings::resources::Settings,
+};
+
+pub struct JuicePlugin;
+
+impl Plugin for JuicePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraShake>()
+            .init_resource::<HitStop>()
+            .init_resource::<ScreenFlash>()
+            .add_systems(Startup, spawn_flash_overlay)
+            .add_systems(
+                Update,
+                (
+                    (player_hurt_juice, kill_juice),
+                    undo_shake.before(camera_follow_system),
+                    shake_camera.after(camera_follow_system),
+                    update_hit_stop,
+                    update_flash,
+                ),
+            );
+    }
+}
+
+/// Trauma from 0 to 1, the camera shakes with its square so small knocks stay
+/// subtle. Anything big enough to be felt adds to it: hits, explosions, stomps.
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    trauma: f32,
+    // what the last frame moved the camera by, taken back before following
+    offset: Vec3,
+}
+
+impl CameraShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+}
+
+/// Slows the game down for a moment to sell a heavy hit. Runs on real time so
+/// the slowdown does not stretch itself.
+#[derive(Resource, Default)]
+pub struct HitStop {
+    timer: Option<Timer>,
+}
+
+impl HitStop {
+    // overlapping hit-stops do not add up, the longest one wins
+    pub fn freeze(&mut self, seconds: f32) {
+        if seconds <= 0.0 {
+            return;
+        }
+        let remaining = self
+            .timer
+            .as_ref()
+            .map_or(0.0, |timer| timer.remaining_secs());
+        if seconds > remaining {
+            self.timer = Some(Timer::from_seconds(seconds, TimerMode::Once));
+        }
+    }
+}
+
+/// A full screen color that fades out over its duration
+#[derive(Resource, Default)]
+pub struct ScreenFlash {
+    color: Color,
+    timer: Option<Timer>,
+}
+
+impl ScreenFlash {
+    pub fn flash(&mut self, color: Color, seconds: f32) {
+        self.color = color;
+        self.timer = Some(Timer::from_seconds(seconds, TimerMode::Once));
+    }
+}
+
+#[derive(Component)]
+struct FlashOverlay;
+
+fn spawn_flash_overlay(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            // above every menu, clicks pass through it
+            z_index: ZIndex::Global(100),
+            ..default()
+        },
+        FlashOverlay,
+    ));
+}
+
+fn player_hurt_juice(
+    query: Query<(), (With<Player>, Changed<Hurting>)>,
+    tuning: Res<Tuning>,
+    settings: Res<Settings>,
+    mut shake: ResMut<CameraShake>,
+    mut hit_stop: ResMut<HitStop>,
+    mut flash: ResMut<ScreenFlash>,
+) {
+    if query.is_empty() {
+        return;
+    }
+
+    let juice = &tuning.juice;
+    let strength = settings.gameplay.screen_effects;
+    shake.add_trauma(juice.hurt_trauma);
+    hit_stop.freeze(juice.hurt_hit_stop * strength);
+    flash.flash(
+        Color::rgba(1.0, 0.0, 0.0, juice.flash_alpha),
+        juice.flash_duration,
+    );
+}
+
+fn kill_juice(
+    mut killed_events: EventReader<EnemyKilledEvent>,
+    tuning: Res<Tuning>,
+    settings: Res<Settings>,
+    mut shake: ResMut<CameraShake>,
+    mut hit_stop: ResMut<HitStop>,
+) {
+    let juice = &tuning.juice;
+    let strength = settings.gameplay.screen_effects;
+    for event in killed_events.read() {
+        match event.kind {
+            EnemyKind::Elite => {
+                shake.add_trauma(juice.elite_kill_trauma);
+                hit_stop.freeze(juice.elite_kill_hit_stop * strength);
+            }
+            EnemyKind::Grunt => hit_stop.freeze(juice.kill_hit_stop * strength),
+        }
+    }
+}
+
+fn undo_shake(mut shake: ResMut<CameraShake>, mut query: Query<&mut Transform, With<WorldCamera>>) {
+    let offset = std::mem::take(&mut shake.offset);
+    for mut transform in &mut query {
+        transform.translation -= offset;
+    }
+}
+
+// smooth noise from -1 to 1, a few sines that never line up
+fn wobble(seed: f32, t: f32) -> f32 {
+    ((t + seed).sin() + 0.5 * (2.3 * t + 1.7 * seed).sin() + 0.25 * (4.1 * t + 0.3 * seed).sin())
+        / 1.75
+}
+
+pub fn shake_camera(
+    time: Res<Time>,
+    tuning: Res<Tuning>,
+    settings: Res<Settings>,
+    mut shake: ResMut<CameraShake>,
+    mut query: Query<&mut Transform, With<WorldCamera>>,
+) {
+    let juice = &tuning.juice;
+    shake.trauma = (shake.trauma - juice.trauma_decay * time.delta_seconds()).max(0.0);
+
+    let amount = shake.trauma * shake.trauma * settings.gameplay.screen_effects;
+    let t = time.elapsed_seconds() * juice.shake_frequency;
+    let offset = Vec3::new(wobble(0.0, t), wobble(10.0, t), 0.0) * juice.shake_offset * amount;
+    let angle = wobble(20.0, t) * juice.shake_angle * amount;
+
+    shake.offset = offset;
+    for mut transform in &mut query {
+        transform.translation += offset;
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+fn update_hit_stop(
+    time: Res<Time<Real>>,
+    tuning: Res<Tuning>,
+    mut hit_stop: ResMut<HitStop>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(timer) = hit_stop.timer.as_mut() else {
+        return;
+    };
+
+    if timer.tick(time.delta()).finished() {
+        hit_stop.timer = None;
+        virtual_time.set_relative_speed(1.0);
+    } else {
+        virtual_time.set_relative_speed(tuning.juice.hit_stop_speed);
+    }
+}
+
+fn update_flash(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    mut flash: ResMut<ScreenFlash>,
+    mut query: Query<&mut BackgroundColor, With<FlashOverlay>>,
+) {
+    let flash = &mut *flash;
+    let mut color = Color::NONE;
+    if let Some(timer) = flash.timer.as_mut() {
+        if timer.tick(time.delta()).finished() {
+            flash.timer = None;
+        } else {
+            let fade = timer.percent_left() * settings.gameplay.screen_effects.clamp(0.0, 1.0);
+            color = flash.color.with_a(flash.color.a() * fade);
+        }
+    }
+
+    for mut background in &mut query {
+        background.0 = color;
+    }
+}
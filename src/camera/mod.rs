@@ -1,2 +1,5 @@
 pub mod components;
+pub mod juice;
+pub mod pixel;
+pub mod resources;
 pub mod systems;
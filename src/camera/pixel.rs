@@ -0,0 +1,239 @@
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+    window::PrimaryWindow,
+};
+
+use super::{
+    components::WorldCamera,
+    juice::shake_camera,
+    resources::{CameraZoom, PixelViewport},
+    systems::camera_follow_system,
+};
+
+// The world is drawn at this resolution and blown up by a whole number, so
+// every art pixel stays square however big the window is. Zooming out adds
+// virtual pixels instead of shrinking them, the same number on every window.
+pub const VIRTUAL_WIDTH: u32 = 256;
+pub const VIRTUAL_HEIGHT: u32 = 144;
+
+// World units per virtual pixel. Sprites are 8px art scaled by 5, so one art
+// pixel is one virtual pixel and the unzoomed world view is 1280x720 units on
+// any window.
+pub const WORLD_UNITS_PER_PIXEL: f32 = 5.0;
+
+// the UI was laid out for this window size, larger windows scale it up
+const UI_REFERENCE_HEIGHT: f32 = 720.0;
+
+// Zoom is rounded down to these steps of the virtual resolution. Each one is a
+// canvas of even size, 1.25 shows 320x180 virtual pixels.
+const ZOOM_STEP: f32 = 0.25;
+
+pub struct PixelPerfectPlugin;
+
+impl Plugin for PixelPerfectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PixelViewport>()
+            .init_resource::<PixelSnap>()
+            .add_systems(
+                Update,
+                (
+                    fit_canvas,
+                    unsnap_camera.before(camera_follow_system),
+                    snap_camera.after(shake_camera),
+                ),
+            );
+    }
+}
+
+/// The sprite showing what the world camera rendered
+#[derive(Component)]
+pub struct Canvas;
+
+// only the upscaling camera sees the canvas
+const CANVAS_LAYER: RenderLayers = RenderLayers::layer(1);
+
+/// The image the world camera renders into
+pub fn canvas_image() -> Image {
+    let size = Extent3d {
+        width: VIRTUAL_WIDTH,
+        height: VIRTUAL_HEIGHT,
+        depth_or_array_layers: 1,
+    };
+
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("canvas"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..default()
+    };
+    // zero filled, drawn over by the camera every frame
+    image.resize(size);
+    image
+}
+
+/// Points the world camera at the canvas and spawns the camera that shows it
+/// in the window. The UI is drawn by that one, at window resolution.
+pub fn spawn_canvas(commands: &mut Commands, camera: &mut Camera, image: Handle<Image>) {
+    camera.target = RenderTarget::Image(image.clone());
+
+    commands.spawn((
+        SpriteBundle {
+            texture: image,
+            ..default()
+        },
+        Canvas,
+        CANVAS_LAYER,
+    ));
+
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                // after the world camera has filled the canvas
+                order: 1,
+                ..default()
+            },
+            camera_2d: Camera2d {
+                // the letterbox bars
+                clear_color: ClearColorConfig::Custom(Color::BLACK),
+            },
+            ..default()
+        },
+        CANVAS_LAYER,
+    ));
+}
+
+/// The largest whole number scale that fits a canvas of `canvas` virtual
+/// pixels into a window of `physical` pixels, never below 1
+fn integer_scale(physical: UVec2, canvas: UVec2) -> u32 {
+    (physical.x / canvas.x).min(physical.y / canvas.y).max(1)
+}
+
+/// Virtual pixels the canvas has when the camera wants `zoom`. It only depends
+/// on the zoom, so every window sees the same part of the world and enemies
+/// spawn at the same distance. Rounded down to a step, never showing more than
+/// asked for, and even so it stays centered.
+fn canvas_size(zoom: f32) -> UVec2 {
+    let step = (zoom.max(1.0) / ZOOM_STEP).floor() * ZOOM_STEP;
+    let size = (Vec2::new(VIRTUAL_WIDTH as f32, VIRTUAL_HEIGHT as f32) * step).round();
+    size.as_uvec2() & !1
+}
+
+// Resizes the canvas to the zoom and scales it to the window, and keeps the UI
+// the same size relative to the game
+fn fit_canvas(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut canvas_query: Query<(&mut Transform, &Handle<Image>), With<Canvas>>,
+    mut images: ResMut<Assets<Image>>,
+    zoom: Res<CameraZoom>,
+    mut viewport: ResMut<PixelViewport>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let physical = UVec2::new(window.physical_width(), window.physical_height());
+    let canvas = canvas_size(zoom.0);
+    // zoomed out it may only fit a smaller scale, the letterbox bars grow
+    let scale = integer_scale(physical, canvas);
+    // the canvas camera works in logical pixels, the scale is in physical ones
+    let logical_scale = scale as f32 / window.scale_factor() as f32;
+    let size = canvas.as_vec2() * logical_scale;
+    let window_size = Vec2::new(window.width(), window.height());
+
+    let fitted = PixelViewport {
+        canvas,
+        scale: logical_scale,
+        offset: ((window_size - size) / 2.0).round(),
+    };
+    if *viewport == fitted {
+        return;
+    }
+
+    let extent = Extent3d {
+        width: canvas.x,
+        height: canvas.y,
+        depth_or_array_layers: 1,
+    };
+    for (mut transform, image) in &mut canvas_query {
+        transform.scale = Vec3::new(logical_scale, logical_scale, 1.0);
+        if let Some(image) = images.get_mut(image) {
+            if image.texture_descriptor.size != extent {
+                image.resize(extent);
+            }
+        }
+    }
+    // by the unzoomed size, the UI does not jump with the zoom steps
+    let unzoomed_scale = integer_scale(physical, UVec2::new(VIRTUAL_WIDTH, VIRTUAL_HEIGHT));
+    let unzoomed_height = (VIRTUAL_HEIGHT * unzoomed_scale) as f32 / window.scale_factor() as f32;
+    ui_scale.0 = (unzoomed_height / UI_REFERENCE_HEIGHT) as f64;
+    *viewport = fitted;
+}
+
+/// How far the world camera was moved onto the pixel grid this frame
+#[derive(Resource, Default)]
+struct PixelSnap(Vec2);
+
+// Takes the snap back so following and shaking work from the smooth position
+fn unsnap_camera(mut snap: ResMut<PixelSnap>, mut query: Query<&mut Transform, With<WorldCamera>>) {
+    let offset = std::mem::take(&mut snap.0);
+    for mut transform in &mut query {
+        transform.translation -= offset.extend(0.0);
+    }
+}
+
+// Puts the world camera on a whole virtual pixel after it followed and shook,
+// so sprites do not shimmer as it glides
+fn snap_camera(mut snap: ResMut<PixelSnap>, mut query: Query<&mut Transform, With<WorldCamera>>) {
+    for mut transform in &mut query {
+        let position = transform.translation.truncate();
+        let snapped = (position / WORLD_UNITS_PER_PIXEL).round() * WORLD_UNITS_PER_PIXEL;
+        snap.0 = snapped - position;
+        transform.translation += snap.0.extend(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_is_the_largest_whole_fit() {
+        let canvas = UVec2::new(VIRTUAL_WIDTH, VIRTUAL_HEIGHT);
+        assert_eq!(integer_scale(UVec2::new(1280, 720), canvas), 5);
+        assert_eq!(integer_scale(UVec2::new(1920, 1080), canvas), 7);
+        // limited by the narrow side
+        assert_eq!(integer_scale(UVec2::new(800, 1080), canvas), 3);
+        assert_eq!(integer_scale(UVec2::new(100, 100), canvas), 1);
+        // a zoomed out canvas fits fewer times
+        assert_eq!(integer_scale(UVec2::new(1280, 720), canvas_size(1.5)), 3);
+    }
+
+    #[test]
+    fn zoom_steps_through_the_virtual_resolution() {
+        assert_eq!(canvas_size(1.0), UVec2::new(256, 144));
+        // not a full step out yet
+        assert_eq!(canvas_size(1.2), UVec2::new(256, 144));
+        assert_eq!(canvas_size(1.25), UVec2::new(320, 180));
+        assert_eq!(canvas_size(1.5), UVec2::new(384, 216));
+        assert_eq!(canvas_size(1.75), UVec2::new(448, 252));
+        assert_eq!(canvas_size(0.5), UVec2::new(256, 144));
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+/// How far the camera wants to be zoomed out, 1.0 shows the virtual resolution.
+/// The canvas follows it in steps of the virtual resolution.
+#[derive(Resource)]
+pub struct CameraZoom(pub f32);
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Where the upscaled canvas sits in the window, in logical window pixels
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct PixelViewport {
+    // virtual pixels the canvas has, more of them the further it is zoomed out
+    pub canvas: UVec2,
+    // window pixels per virtual pixel
+    pub scale: f32,
+    // size of the letterbox bars on the left and top
+    pub offset: Vec2,
+}
+
+impl Default for PixelViewport {
+    fn default() -> Self {
+        Self {
+            canvas: UVec2::ZERO,
+            scale: 1.0,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+impl PixelViewport {
+    /// A window position, like the cursor, in virtual pixels of the world
+    /// camera's viewport. None on the letterbox bars.
+    pub fn to_virtual(&self, window_position: Vec2, virtual_size: Vec2) -> Option<Vec2> {
+        let position = (window_position - self.offset) / self.scale;
+        let inside = position.cmpge(Vec2::ZERO).all() && position.cmplt(virtual_size).all();
+        inside.then_some(position)
+    }
+}
@@ -1,18 +1,26 @@
-use crate::game::components::GameState;
+use crate::{enemy::components::Enemy, game::components::GameState, health::components::Dead};
 
-use super::components::*;
+use super::{
+    components::*,
+    juice::JuicePlugin,
+    pixel::{canvas_image, spawn_canvas, PixelPerfectPlugin, WORLD_UNITS_PER_PIXEL},
+    resources::CameraZoom,
+};
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
+        app.add_plugins((PixelPerfectPlugin, JuicePlugin));
+        app.init_resource::<CameraZoom>();
         app.add_systems(Startup, spawn_camera);
 
         // Playing
         app.add_systems(
             Update,
-            camera_follow_system.run_if(in_state(GameState::Playing)),
+            (camera_follow_system, zoom_camera).run_if(in_state(GameState::Playing)),
         );
 
         // Upgrade
@@ -23,25 +31,48 @@ impl Plugin for CameraPlugin {
     }
 }
 
-fn spawn_camera(mut commands: Commands) {
+fn spawn_camera(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scale = WORLD_UNITS_PER_PIXEL;
+    spawn_canvas(
+        &mut commands,
+        &mut camera.camera,
+        images.add(canvas_image()),
+    );
+
     commands.spawn((
-        Camera2dBundle::default(),
+        camera,
+        // the UI is drawn at window resolution by the canvas camera
+        UiCameraConfig { show_ui: false },
+        WorldCamera,
         CameraFollow {
             acceleration: 1.0,
             smoothness: 0.5,
             target_position: Vec3::ZERO,
+            look_ahead: 80.0,
+            min_zoom: 1.0,
+            max_zoom: 1.5,
+            zoom_enemies: 60.0,
+            zoom_speed: 0.5,
         },
     ));
 }
 
-fn camera_follow_system(
+pub fn camera_follow_system(
     time: Res<Time>,
-    mut query: Query<(&CameraFollow, &mut Transform), With<Camera>>,
-    target_query: Query<&Transform, (With<Target>, Without<Camera>)>,
+    mut query: Query<(&mut CameraFollow, &mut Transform), With<WorldCamera>>,
+    target_query: Query<(&Transform, &Target, Option<&Velocity>), Without<WorldCamera>>,
 ) {
-    if let Ok(target_transform) = target_query.get_single() {
-        for (camera_follow, mut transform) in query.iter_mut() {
-            let direction = target_transform.translation - transform.translation;
+    if let Ok((target_transform, target, velocity)) = target_query.get_single() {
+        // leads towards where the target is going and where it is aiming
+        let moving = velocity.map_or(Vec2::ZERO, |velocity| velocity.linvel.normalize_or_zero());
+        let lead = (moving + target.aim.normalize_or_zero()).clamp_length_max(1.0);
+
+        for (mut camera_follow, mut transform) in query.iter_mut() {
+            camera_follow.target_position =
+                target_transform.translation + (lead * camera_follow.look_ahead).extend(0.0);
+
+            let direction = camera_follow.target_position - transform.translation;
             let acceleration = direction * camera_follow.acceleration;
 
             // Apply smoothing
@@ -50,3 +81,22 @@ fn camera_follow_system(
         }
     }
 }
+
+// Zooms out as more enemies are alive, so a crowd still fits on screen. The
+// projection keeps its scale, the canvas grows to show more of the world.
+fn zoom_camera(
+    time: Res<Time>,
+    mut zoom: ResMut<CameraZoom>,
+    query: Query<&CameraFollow, With<WorldCamera>>,
+    enemy_query: Query<(), (With<Enemy>, Without<Dead>)>,
+) {
+    let enemies = enemy_query.iter().count() as f32;
+    for camera_follow in &query {
+        let crowd = (enemies / camera_follow.zoom_enemies.max(1.0)).min(1.0);
+        let target =
+            camera_follow.min_zoom + (camera_follow.max_zoom - camera_follow.min_zoom) * crowd;
+
+        let step = (camera_follow.zoom_speed * time.delta_seconds()).min(1.0);
+        zoom.0 += (target - zoom.0) * step;
+    }
+}
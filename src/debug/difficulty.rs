@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use crate::game::difficulty::Difficulty;
+
+pub struct DifficultyDebugPlugin;
+
+impl Plugin for DifficultyDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_difficulty_overlay);
+        app.add_systems(
+            Update,
+            (difficulty_text_update_system, difficulty_overlay_showhide),
+        );
+    }
+}
+
+/// Marker to find the container entity so we can show/hide the overlay
+#[derive(Component)]
+struct DifficultyRoot;
+
+/// Marker to find the text entity so we can update it
+#[derive(Component)]
+struct DifficultyText;
+
+fn setup_difficulty_overlay(mut commands: Commands) {
+    commands
+        .spawn((
+            DifficultyRoot,
+            NodeBundle {
+                background_color: BackgroundColor(Color::BLACK.with_a(0.5)),
+                z_index: ZIndex::Global(i32::MAX),
+                visibility: Visibility::Hidden,
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    // below the fps counter in the top-right corner
+                    right: Val::Percent(1.),
+                    top: Val::Px(40.),
+                    padding: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                DifficultyText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+            ));
+        });
+}
+
+fn difficulty_text_update_system(
+    difficulty: Res<Difficulty>,
+    mut query: Query<&mut Text, With<DifficultyText>>,
+) {
+    if !difficulty.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.sections[0].value = format!(
+            "time: {:>6.1}s\nspawn: {:>5.2}s\nhealth: x{:.2}\nspeed: x{:.2}\nelite: {:>4.1}%",
+            difficulty.elapsed,
+            difficulty.spawn_interval,
+            difficulty.health_multiplier,
+            difficulty.speed_multiplier,
+            difficulty.elite_chance * 100.0,
+        );
+    }
+}
+
+/// Toggle the difficulty overlay when pressing F11
+fn difficulty_overlay_showhide(
+    mut q: Query<&mut Visibility, With<DifficultyRoot>>,
+    kbd: Res<Input<KeyCode>>,
+) {
+    if kbd.just_pressed(KeyCode::F11) {
+        let mut vis = q.single_mut();
+        *vis = match *vis {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
@@ -1 +1,2 @@
+pub mod difficulty;
 pub mod fps;
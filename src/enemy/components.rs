@@ -1,10 +1,8 @@
-use crate::base::components::*;
+use crate::{animation::components::AnimState, game::tuning::EnemyTuning};
 use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct Enemy {
-    pub idle: AnimationIndices,
-    pub walk: AnimationIndices,
     pub state: EnemyState,
     pub stats: EnemyStats,
 }
@@ -23,25 +21,55 @@ pub struct EnemyStats {
 #[derive(Component)]
 pub struct TagEnemy;
 
-impl Default for Enemy {
-    fn default() -> Self {
+// Tags in enemy_3.sprite.json
+pub const ENEMY_ANIMATIONS: &[(AnimState, &str)] = &[
+    (AnimState::Idle, "idle"),
+    (AnimState::Move, "walk"),
+    (AnimState::Attack, "attack"),
+    (AnimState::Hurt, "hurt"),
+    (AnimState::Death, "death"),
+];
+
+// Tougher and bigger variant of a regular enemy
+#[derive(Component)]
+pub struct Elite;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EnemyKind {
+    Grunt,
+    Elite,
+}
+
+impl EnemyKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyKind::Grunt => "Grunt",
+            EnemyKind::Elite => "Elite",
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct EnemyKilledEvent {
+    pub kind: EnemyKind,
+    pub location: Vec3,
+}
+
+pub const ELITE_HEALTH_MULTIPLIER: f32 = 3.0;
+// whole virtual pixels per art pixel, like every other sprite
+pub const ELITE_SIZE_MULTIPLIER: f32 = 2.0;
+
+impl Enemy {
+    pub fn new(tuning: &EnemyTuning) -> Self {
         Self {
-            idle: AnimationIndices {
-                first: 321,
-                last: 321,
-            },
-            walk: AnimationIndices {
-                first: 322,
-                last: 324,
-            },
             state: EnemyState {
                 moving: false,
                 facing: Vec3::new(1.0, 0.0, 0.0),
                 attack: false,
             },
             stats: EnemyStats {
-                size: 5.0,
-                move_speed: 100.0,
+                size: tuning.size,
+                move_speed: tuning.move_speed,
             },
         }
     }
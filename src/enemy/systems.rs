@@ -1,27 +1,34 @@
-use bevy::sprite::Anchor;
-use bevy::{prelude::*, window::PrimaryWindow};
+use std::time::Duration;
+
+use bevy::prelude::*;
 use bevy_asepritesheet::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use super::components::*;
 use super::resources::*;
 
-use crate::audio::components::{PlaySoundEffectEvent, SoundEffectType};
-use crate::base::components::WINDOW_PADDING;
+use crate::animation::{components::*, systems::AnimationSet};
+use crate::audio::components::{SoundCue, SoundCueEvent};
+use crate::camera::components::WorldCamera;
 use crate::damagable::components::Damageable;
-use crate::game::components::{GameRules, GameState};
+use crate::game::components::GameState;
+use crate::game::difficulty::Difficulty;
+use crate::game::run::{OnRunStart, RunScoped};
+use crate::game::tuning::Tuning;
 use crate::health::components::{Dead, Health};
 use crate::hurt::components::*;
+use crate::loading::resources::SpriteSheets;
 use crate::particle::components::Particle;
-use crate::player::components::{Bullet, Player};
+use crate::player::components::{Bullet, DamageDealtEvent, Player};
 use crate::xp::components::XPDropEvent;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), setup_enemy_timer)
-            .add_systems(OnExit(GameState::Playing), cleanup);
+        app.add_event::<EnemyKilledEvent>();
+
+        app.add_systems(OnRunStart, setup_enemy_timer);
 
         // Playing
         app.add_systems(
@@ -31,7 +38,7 @@ impl Plugin for EnemyPlugin {
                 move_enemy,
                 hurt_enemy,
                 flip_enemy,
-                animate_enemy,
+                animate_enemy.in_set(AnimationSet),
                 kill_enemy,
                 cleanup_dead,
             )
@@ -42,7 +49,14 @@ impl Plugin for EnemyPlugin {
         // Upgrade
         app.add_systems(
             Update,
-            (animate_enemy, pause_move, cleanup_dead).run_if(in_state(GameState::Upgrade)),
+            (animate_enemy.in_set(AnimationSet), pause_move, cleanup_dead)
+                .run_if(in_state(GameState::Upgrade)),
+        );
+
+        // Game over
+        app.add_systems(
+            Update,
+            (animate_enemy.in_set(AnimationSet), pause_move).run_if(in_state(GameState::GameOver)),
         );
     }
 }
@@ -55,145 +69,147 @@ fn pause_move(mut query: Query<(&mut Velocity, &mut Enemy), With<Enemy>>) {
     }
 }
 
-fn setup_enemy_timer(mut commands: Commands, game: Res<GameRules>) {
-    println!("Setting up enemy timer: {} sec", game.enemy_spawn_interval);
-    commands.spawn(EnemySpawnTimer(Timer::from_seconds(
-        game.enemy_spawn_interval,
-        TimerMode::Repeating,
-    )));
-}
-
-fn cleanup(mut commands: Commands, query: Query<Entity, With<EnemySpawnTimer>>) {
-    for entity in &mut query.iter() {
-        commands.entity(entity).despawn();
-    }
+pub fn setup_enemy_timer(mut commands: Commands, difficulty: Res<Difficulty>) {
+    commands.spawn((
+        EnemySpawnTimer(Timer::from_seconds(
+            difficulty.spawn_interval,
+            TimerMode::Repeating,
+        )),
+        RunScoped,
+    ));
 }
 
-fn spawn_enemies(
+pub fn spawn_enemies(
     mut commands: Commands,
     mut query: Query<&mut EnemySpawnTimer>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &GlobalTransform)>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<WorldCamera>>,
     time: Res<Time>,
-    asset_server: Res<AssetServer>,
-    _gizmos: Gizmos,
+    sprite_sheets: Res<SpriteSheets>,
+    difficulty: Res<Difficulty>,
+    tuning: Res<Tuning>,
 ) {
-    let spritesheet_handle = load_spritesheet_then(
-        &mut commands,
-        &asset_server,
-        "enemy_3.sprite.json",
-        Anchor::Center,
-        |sheet| {
-            let handle_death = sheet.get_anim_handle("death");
-
-            if let Ok(anim_death) = sheet.get_anim_mut(&handle_death) {
-                anim_death.end_action = AnimEndAction::Pause;
-            }
-        },
+    let padding = tuning.spawn_padding;
+    let (camera_transform, projection) = camera_query.single();
+    let view = Rect::from_center_size(
+        camera_transform.translation().truncate(),
+        projection.area.size(),
     );
-
-    let window = window_query.get_single().unwrap();
-    let (camera, camera_transform) = camera_query.single();
     for mut timer in &mut query {
+        timer
+            .0
+            .set_duration(Duration::from_secs_f32(difficulty.spawn_interval));
+
         if timer.0.tick(time.delta()).just_finished() {
             let horizontal = rand::random::<bool>();
 
             let ver_flip = rand::random::<bool>();
             let hor_flip = rand::random::<bool>();
 
-            let random_coordinates = if horizontal {
-                let random_x = rand::random::<f32>() * window.width();
+            // just outside what the camera sees, however far it is zoomed out
+            let world_coordinates = if horizontal {
+                let random_x = view.min.x + rand::random::<f32>() * view.width();
                 let random_y = if ver_flip {
-                    window.height() + WINDOW_PADDING
+                    view.max.y + padding
                 } else {
-                    -WINDOW_PADDING
+                    view.min.y - padding
                 };
                 Vec2::new(random_x, random_y)
             } else {
                 let random_x = if hor_flip {
-                    window.width() + WINDOW_PADDING
+                    view.max.x + padding
                 } else {
-                    -WINDOW_PADDING
+                    view.min.x - padding
                 };
-                let random_y = rand::random::<f32>() * window.height();
+                let random_y = view.min.y + rand::random::<f32>() * view.height();
                 Vec2::new(random_x, random_y)
             };
-            let _no_so_random = Vec2::new(window.width(), window.height());
-            let Some(world_coordinates) =
-                camera.viewport_to_world_2d(camera_transform, random_coordinates)
-            else {
-                return;
+
+            let elite = rand::random::<f32>() < difficulty.elite_chance;
+            let mut enemy = Enemy::new(&tuning.enemy);
+            let mut health = tuning.enemy.health * difficulty.health_multiplier;
+            enemy.stats.move_speed *= difficulty.speed_multiplier;
+            if elite {
+                enemy.stats.size *= ELITE_SIZE_MULTIPLIER;
+                health *= ELITE_HEALTH_MULTIPLIER;
+            }
+            let size = enemy.stats.size;
+
+            let mut enemy_commands = commands.spawn((
+                SpatialBundle::from(Transform::from_xyz(
+                    world_coordinates.x,
+                    world_coordinates.y,
+                    0.5,
+                )),
+                RigidBody::Dynamic,
+                Damageable,
+                Health {
+                    max: health,
+                    current: health,
+                },
+                Velocity::zero(),
+                ActiveEvents::COLLISION_EVENTS,
+                LockedAxes::ROTATION_LOCKED,
+                enemy,
+                TagEnemy,
+                RunScoped,
+            ));
+
+            if elite {
+                enemy_commands.insert(Elite);
+            }
+
+            let tints = if elite {
+                TintStack::new(&[Tint::Elite])
+            } else {
+                TintStack::default()
             };
 
-            commands
-                .spawn((
-                    SpatialBundle::from(Transform::from_xyz(
-                        world_coordinates.x,
-                        world_coordinates.y,
-                        0.5,
-                    )),
-                    RigidBody::Dynamic,
-                    Damageable,
-                    Health {
-                        max: 3.0,
-                        current: 3.0,
-                    },
-                    Velocity::zero(),
-                    ActiveEvents::COLLISION_EVENTS,
-                    LockedAxes::ROTATION_LOCKED,
-                    Enemy::default(),
+            enemy_commands.with_children(|parent| {
+                parent.spawn((
+                    Collider::ball(size * 3.0),
+                    TransformBundle::from(Transform::from_xyz(0.0, -5.0, 0.0)),
+                    Damage(tuning.enemy.damage),
                     TagEnemy,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        Collider::ball(15.0),
-                        TransformBundle::from(Transform::from_xyz(0.0, -5.0, 0.0)),
-                        Damage(1.0),
-                        TagEnemy,
-                    ));
-
-                    parent.spawn((
-                        AnimatedSpriteBundle {
-                            spritesheet: spritesheet_handle.clone(),
-                            sprite_bundle: SpriteSheetBundle {
-                                transform: Transform {
-                                    translation: Vec3::ZERO,
-                                    scale: Vec3::splat(Enemy::default().stats.size),
-                                    ..default()
-                                },
+                ));
+
+                parent.spawn((
+                    AnimatedSpriteBundle {
+                        spritesheet: sprite_sheets.enemy.clone(),
+                        sprite_bundle: SpriteSheetBundle {
+                            transform: Transform {
+                                translation: Vec3::ZERO,
+                                scale: Vec3::splat(size),
                                 ..default()
                             },
                             ..default()
                         },
-                        AnimEventSender,
-                        TagEnemy,
-                    ));
-                });
+                        ..default()
+                    },
+                    AnimEventSender,
+                    AnimationController::new(ENEMY_ANIMATIONS),
+                    tints,
+                    TagEnemy,
+                ));
+            });
         }
     }
 }
 
 fn animate_enemy(
-    mut q_animator: Query<&mut SpriteAnimator, With<TagEnemy>>,
+    mut q_controller: Query<&mut AnimationController, With<TagEnemy>>,
     q_enemy: Query<(&Health, &Enemy, &Children)>,
 ) {
     for (health, enemy, children) in &q_enemy {
         for child in children {
-            if let Ok(mut sprite_animator) = q_animator.get_mut(*child) {
-                sprite_animator.time_scale = 1.0;
-
+            if let Ok(mut controller) = q_controller.get_mut(*child) {
                 if health.current <= 0.0 {
-                    // death
-                    sprite_animator.set_anim_index(4);
+                    controller.set_state(AnimState::Death);
                 } else if enemy.state.attack {
-                    //attack
-                    sprite_animator.set_anim_index(2);
+                    controller.set_state(AnimState::Attack);
                 } else if enemy.state.moving {
-                    // walk
-                    sprite_animator.set_anim_index(0);
+                    controller.set_state(AnimState::Move);
                 } else {
-                    // idle
-                    sprite_animator.set_anim_index(1);
+                    controller.set_state(AnimState::Idle);
                 }
             }
         }
@@ -202,16 +218,12 @@ fn animate_enemy(
 
 fn flip_enemy(
     query: Query<(&Enemy, &Children), Without<Dead>>,
-    mut sprite_query: Query<&mut Transform, With<TextureAtlasSprite>>,
+    mut sprite_query: Query<&mut TextureAtlasSprite>,
 ) {
     for (enemy, children) in &query {
         for children in children {
-            if let Ok(mut sprite_transform) = sprite_query.get_mut(*children) {
-                if enemy.state.facing.x < 0.0 {
-                    sprite_transform.scale.x = -enemy.stats.size;
-                } else {
-                    sprite_transform.scale.x = enemy.stats.size;
-                }
+            if let Ok(mut sprite) = sprite_query.get_mut(*children) {
+                sprite.flip_x = enemy.state.facing.x < 0.0;
             }
         }
     }
@@ -220,6 +232,7 @@ fn flip_enemy(
 fn move_enemy(
     mut enemy_query: Query<(&mut Enemy, &Transform, &mut Velocity, &Health)>,
     player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    tuning: Res<Tuning>,
 ) {
     let player_transform = player_query.single();
     for (mut enemy, transform, mut vel, health) in &mut enemy_query {
@@ -233,7 +246,7 @@ fn move_enemy(
         enemy.state.facing = Vec3::new(move_delta.x, move_delta.y, 0.0);
 
         // set attack state
-        enemy.state.attack = distance < 60.0;
+        enemy.state.attack = distance < tuning.enemy.attack_range;
 
         // set velocity
         if health.current > 0.0 {
@@ -250,13 +263,16 @@ fn hurt_enemy(
     mut enemy_collider_query: Query<Entity, With<TagEnemy>>,
     enemy_query: Query<&Transform, (With<Enemy>, Without<Player>)>,
     parent_query: Query<&Parent, &Transform>,
-    damage_query: Query<(Entity, &Damage), With<Bullet>>,
+    damage_query: Query<(Entity, &Damage, &Bullet)>,
     rapier_context: Res<RapierContext>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    mut sound_event: EventWriter<SoundCueEvent>,
+    mut damage_event: EventWriter<DamageDealtEvent>,
+    tuning: Res<Tuning>,
 ) {
+    let particles = &tuning.particles;
     for _ in collision_events.read() {
         for enemy_collider_entity in &mut enemy_collider_query {
-            for (damage_entity, damage_source) in &damage_query {
+            for (damage_entity, damage_source, bullet) in &damage_query {
                 if let Some(contact_pair) =
                     rapier_context.contact_pair(damage_entity, enemy_collider_entity)
                 {
@@ -266,6 +282,10 @@ fn hurt_enemy(
                         // despawn damage source
                         commands.entity(damage_entity).despawn();
                         commands.entity(parent).insert(Hurting(damage_source.0));
+                        damage_event.send(DamageDealtEvent {
+                            weapon: bullet.weapon,
+                            amount: damage_source.0,
+                        });
 
                         let mut normal: Vec2 = Vec2::ZERO;
 
@@ -274,24 +294,33 @@ fn hurt_enemy(
                         }
 
                         // play sound effect
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::EnemyHurt,
+                        let position = Some(enemy_transform.translation.truncate());
+                        sound_event.send(SoundCueEvent {
+                            cue: SoundCue::EnemyHit,
+                            position,
+                        });
+                        sound_event.send(SoundCueEvent {
+                            cue: SoundCue::BulletImpact,
+                            position,
                         });
 
-                        // Spawn 3-5 particles in the opposite direction of the collision normal
-
-                        let particle_amount = rand::random::<f32>() * 5.0 + 5.0;
+                        // Spawn particles in the opposite direction of the collision normal
+                        let particle_amount = particles.count_min as f32
+                            + rand::random::<f32>()
+                                * particles.count_max.saturating_sub(particles.count_min) as f32;
                         for _ in 0..particle_amount as u32 {
                             let tangent = Vec2::new(normal.y, -normal.x);
                             let spread = rand::random::<f32>() * 2.0 - 1.0;
-                            let speed = rand::random::<f32>() * 0.05;
-                            let velocity = (normal + tangent * spread * 3.0) * speed;
-                            let lifetime = rand::random::<f32>() * 0.5 + 0.3;
+                            let speed = rand::random::<f32>() * particles.speed;
+                            let velocity = (normal + tangent * spread * particles.spread) * speed;
+                            let lifetime = particles.lifetime_min
+                                + rand::random::<f32>()
+                                    * (particles.lifetime_max - particles.lifetime_min);
 
                             commands.spawn((
                                 SpriteBundle {
                                     sprite: Sprite {
-                                        custom_size: Some(Vec2::splat(3.0)),
+                                        custom_size: Some(Vec2::splat(particles.size)),
                                         color: Color::rgba(1.0, 0.0, 0.0, 1.0),
                                         ..default()
                                     },
@@ -306,12 +335,13 @@ fn hurt_enemy(
                                     ..default()
                                 },
                                 RigidBody::Dynamic,
+                                RunScoped,
                                 Particle {
                                     initial_position: enemy_transform.translation,
                                     velocity,
                                     max_lifetime: lifetime,
                                     lifetime,
-                                    ..default()
+                                    gravity: particles.gravity,
                                 },
                             ));
                         }
@@ -325,13 +355,29 @@ fn hurt_enemy(
 fn kill_enemy(
     mut commands: Commands,
     mut enemy_query: Query<
-        (Entity, &Health, &Transform, &mut Velocity, &Children),
+        (
+            Entity,
+            &Health,
+            &Transform,
+            &mut Velocity,
+            &Children,
+            Option<&Elite>,
+        ),
         (With<Enemy>, Without<Dead>),
     >,
     mut event_drop_xp: EventWriter<XPDropEvent>,
+    mut event_killed: EventWriter<EnemyKilledEvent>,
 ) {
-    for (entity, health, enemy_transform, mut vel, children) in &mut enemy_query {
+    for (entity, health, enemy_transform, mut vel, children, elite) in &mut enemy_query {
         if health.current <= 0.0 {
+            event_killed.send(EnemyKilledEvent {
+                kind: if elite.is_some() {
+                    EnemyKind::Elite
+                } else {
+                    EnemyKind::Grunt
+                },
+                location: enemy_transform.translation,
+            });
             event_drop_xp.send(XPDropEvent {
                 amount: 1,
                 location: enemy_transform.translation,
@@ -1,24 +1,28 @@
 use bevy::prelude::*;
 
+use super::progression::ProgressionCurve;
+
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum GameState {
     #[default]
     Loading,
+    AssetError,
     Splash,
     Menu,
     Playing,
     Upgrade,
+    Paused,
+    GameOver,
 }
 
 #[derive(Resource)]
 pub struct GameRules {
     pub xp: u32,
     pub level: u32,
-    // the amount of xp the player needs to advance
-    pub level_xp_base: u32,
-    // the amount of additonal xp the player neeeds per level (multiplied)
-    pub level_xp_multiplier: f32,
-    pub enemy_spawn_interval: f32,
+    // levels gained that still need an upgrade to be picked
+    pub pending_level_ups: u32,
+    // the amount of xp the player needs to advance per level
+    pub progression: ProgressionCurve,
 }
 
 impl Default for GameRules {
@@ -26,16 +30,31 @@ impl Default for GameRules {
         Self {
             xp: 0,
             level: 0,
-            level_xp_base: 10,
-            level_xp_multiplier: 1.0,
-            enemy_spawn_interval: 2.0,
+            pending_level_ups: 0,
+            progression: ProgressionCurve::default(),
         }
     }
 }
 
 impl GameRules {
     pub fn get_level_xp(&self) -> u32 {
-        (self.level_xp_base as f32 * self.level_xp_multiplier) as u32
+        self.progression.xp_for_level(self.level)
+    }
+
+    /// Adds xp and advances as many levels as it covers, carrying the rest over.
+    /// Returns the number of levels gained.
+    pub fn add_xp(&mut self, amount: u32) -> u32 {
+        self.xp += amount;
+
+        let mut levels = 0;
+        while self.xp >= self.get_level_xp() {
+            self.xp -= self.get_level_xp();
+            self.level += 1;
+            levels += 1;
+        }
+
+        self.pending_level_ups += levels;
+        levels
     }
 
     pub fn set_level(&mut self, level: u32) {
@@ -49,7 +68,49 @@ impl GameRules {
     pub fn reset(&mut self) {
         self.xp = 0;
         self.level = 0;
-        self.level_xp_multiplier = 1.0;
-        self.enemy_spawn_interval = 2.0;
+        self.pending_level_ups = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(table: Vec<u32>) -> GameRules {
+        GameRules {
+            progression: ProgressionCurve::Table(table),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn excess_xp_carries_over() {
+        let mut game = rules(vec![10, 20]);
+
+        assert_eq!(game.add_xp(13), 1);
+        assert_eq!(game.level, 1);
+        assert_eq!(game.xp, 3);
+    }
+
+    #[test]
+    fn big_pickup_queues_multiple_level_ups() {
+        let mut game = rules(vec![2, 3, 4]);
+
+        assert_eq!(game.add_xp(10), 3);
+        assert_eq!(game.level, 3);
+        assert_eq!(game.xp, 1);
+        assert_eq!(game.pending_level_ups, 3);
+    }
+
+    #[test]
+    fn reset_keeps_progression() {
+        let mut game = rules(vec![2]);
+        game.add_xp(5);
+        game.reset();
+
+        assert_eq!(game.level, 0);
+        assert_eq!(game.xp, 0);
+        assert_eq!(game.pending_level_ups, 0);
+        assert_eq!(game.progression, ProgressionCurve::Table(vec![2]));
     }
 }
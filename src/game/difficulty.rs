@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::components::{GameRules, GameState};
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Difficulty>().add_systems(
+            Update,
+            update_difficulty.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Tuning values for how fast the game gets harder. Every value grows
+/// linearly with elapsed minutes and player level and is clamped to its limit.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DifficultyConfig {
+    // seconds between enemy spawns at the start of a run
+    pub spawn_interval_base: f32,
+    pub spawn_interval_min: f32,
+    pub spawn_interval_per_minute: f32,
+    pub spawn_interval_per_level: f32,
+    pub health_per_minute: f32,
+    pub health_per_level: f32,
+    pub health_max: f32,
+    pub speed_per_minute: f32,
+    pub speed_per_level: f32,
+    pub speed_max: f32,
+    pub elite_chance_per_minute: f32,
+    pub elite_chance_per_level: f32,
+    pub elite_chance_max: f32,
+}
+
+impl Default for DifficultyConfig {
+    fn default() -> Self {
+        Self {
+            spawn_interval_base: 2.0,
+            spawn_interval_min: 0.25,
+            spawn_interval_per_minute: 0.15,
+            spawn_interval_per_level: 0.1,
+            health_per_minute: 0.25,
+            health_per_level: 0.05,
+            health_max: 8.0,
+            speed_per_minute: 0.05,
+            speed_per_level: 0.01,
+            speed_max: 2.0,
+            elite_chance_per_minute: 0.02,
+            elite_chance_per_level: 0.005,
+            elite_chance_max: 0.25,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Difficulty {
+    pub config: DifficultyConfig,
+    // seconds spent in the current run
+    pub elapsed: f32,
+    pub spawn_interval: f32,
+    pub health_multiplier: f32,
+    pub speed_multiplier: f32,
+    pub elite_chance: f32,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        let mut difficulty = Self {
+            config: DifficultyConfig::default(),
+            elapsed: 0.0,
+            spawn_interval: 0.0,
+            health_multiplier: 1.0,
+            speed_multiplier: 1.0,
+            elite_chance: 0.0,
+        };
+        difficulty.update(0.0, 0);
+        difficulty
+    }
+}
+
+impl Difficulty {
+    pub fn update(&mut self, elapsed: f32, level: u32) {
+        let config = &self.config;
+        let minutes = elapsed / 60.0;
+        let level = level as f32;
+
+        self.elapsed = elapsed;
+        self.spawn_interval = (config.spawn_interval_base
+            - config.spawn_interval_per_minute * minutes
+            - config.spawn_interval_per_level * level)
+            .clamp(config.spawn_interval_min, config.spawn_interval_base);
+        self.health_multiplier =
+            (1.0 + config.health_per_minute * minutes + config.health_per_level * level)
+                .clamp(1.0, config.health_max);
+        self.speed_multiplier =
+            (1.0 + config.speed_per_minute * minutes + config.speed_per_level * level)
+                .clamp(1.0, config.speed_max);
+        self.elite_chance = (config.elite_chance_per_minute * minutes
+            + config.elite_chance_per_level * level)
+            .clamp(0.0, config.elite_chance_max);
+    }
+
+    pub fn reset(&mut self) {
+        self.update(0.0, 0);
+    }
+}
+
+fn update_difficulty(mut difficulty: ResMut<Difficulty>, game: Res<GameRules>, time: Res<Time>) {
+    let elapsed = difficulty.elapsed + time.delta_seconds();
+    difficulty.update(elapsed, game.level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_at_base_values() {
+        let difficulty = Difficulty::default();
+
+        assert_eq!(difficulty.spawn_interval, 2.0);
+        assert_eq!(difficulty.health_multiplier, 1.0);
+        assert_eq!(difficulty.speed_multiplier, 1.0);
+        assert_eq!(difficulty.elite_chance, 0.0);
+    }
+
+    #[test]
+    fn long_runs_are_clamped() {
+        let mut difficulty = Difficulty::default();
+        difficulty.update(60.0 * 600.0, 500);
+
+        assert_eq!(
+            difficulty.spawn_interval,
+            difficulty.config.spawn_interval_min
+        );
+        assert_eq!(difficulty.health_multiplier, difficulty.config.health_max);
+        assert_eq!(difficulty.speed_multiplier, difficulty.config.speed_max);
+        assert_eq!(difficulty.elite_chance, difficulty.config.elite_chance_max);
+    }
+}
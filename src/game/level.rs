@@ -1,28 +1,45 @@
 use super::components::{GameRules, GameState};
+use super::progression::ProgressionAsset;
+use crate::base::loader::RonAssetPlugin;
 use bevy::prelude::*;
 
 pub struct LevelPlugin;
 
+#[derive(Resource)]
+struct ProgressionHandle(Handle<ProgressionAsset>);
+
 impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, level_up.run_if(in_state(GameState::Playing)));
+        app.add_plugins(RonAssetPlugin::<ProgressionAsset>::new(&[
+            "progression.ron",
+        ]))
+        .add_systems(Startup, load_progression)
+        .add_systems(Update, apply_progression)
+        .add_systems(Update, level_up.run_if(in_state(GameState::Playing)));
     }
 }
 
-fn level_up(mut game: ResMut<GameRules>, mut next_state: ResMut<NextState<GameState>>) {
-    if game.xp >= game.get_level_xp() {
-        next_state.set(GameState::Upgrade);
-        let current_level = game.level;
-        game.set_level(current_level + 1);
-        game.level_xp_multiplier += 0.2;
-        game.xp = 0;
+fn load_progression(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ProgressionHandle(asset_server.load("progression.ron")));
+}
 
-        //supstract more in the beginning to make the game harder faster
-        //
-        if current_level < 5 {
-            game.enemy_spawn_interval -= 0.3;
-        } else {
-            game.enemy_spawn_interval -= 0.1;
+fn apply_progression(
+    mut asset_events: EventReader<AssetEvent<ProgressionAsset>>,
+    progressions: Res<Assets<ProgressionAsset>>,
+    handle: Res<ProgressionHandle>,
+    mut game: ResMut<GameRules>,
+) {
+    for event in asset_events.read() {
+        if event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0) {
+            if let Some(progression) = progressions.get(&handle.0) {
+                game.progression = progression.curve.clone();
+            }
         }
     }
 }
+
+fn level_up(game: Res<GameRules>, mut next_state: ResMut<NextState<GameState>>) {
+    if game.pending_level_ups > 0 {
+        next_state.set(GameState::Upgrade);
+    }
+}
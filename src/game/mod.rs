@@ -1,19 +1,31 @@
 pub mod components;
+pub mod difficulty;
 pub mod level;
+pub mod pause;
+pub mod progression;
+pub mod run;
+pub mod stats;
 pub mod systems;
+pub mod tuning;
 
-use crate::base::resources::SpriteSheetPlugin;
+use crate::animation::systems::AnimationPlugin;
 use crate::camera::systems::CameraPlugin;
 use crate::enemy::systems::EnemyPlugin;
 use crate::hurt::systems::HurtPlugin;
 use crate::particle::systems::ParticlePlugin;
 use crate::player::systems::PlayerPlugin;
 use crate::ui::UIPlugin;
+use crate::upgrade::UpgradePlugin;
 use crate::xp::systems::XPPlugin;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
+use self::difficulty::DifficultyPlugin;
 use self::level::LevelPlugin;
+use self::pause::PausePlugin;
+use self::run::RunPlugin;
+use self::stats::StatsPlugin;
+use self::tuning::TuningPlugin;
 
 pub struct GamePlugin;
 
@@ -21,7 +33,6 @@ impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<components::GameRules>()
             .add_plugins((
-                SpriteSheetPlugin,
                 PlayerPlugin,
                 EnemyPlugin,
                 HurtPlugin,
@@ -30,6 +41,13 @@ impl Plugin for GamePlugin {
                 ParticlePlugin,
                 UIPlugin,
                 LevelPlugin,
+                DifficultyPlugin,
+                PausePlugin,
+                StatsPlugin,
+                RunPlugin,
+                UpgradePlugin,
+                AnimationPlugin,
+                TuningPlugin,
             ))
             .add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
     }
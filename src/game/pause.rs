@@ -0,0 +1,104 @@
+use bevy::{prelude::*, window::WindowFocused};
+use bevy_kira_audio::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use super::components::GameState;
+use crate::audio::components::{MusicChannel, SfxChannel, StemChannel};
+use crate::ui::settings_menu::{settings_menu_open, SettingsMenuSet};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (pause_game, pause_on_focus_lost).run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(
+            Update,
+            // Escape closes the settings screen first
+            resume_game
+                .before(SettingsMenuSet)
+                .run_if(in_state(GameState::Paused).and_then(not(settings_menu_open))),
+        )
+        .add_systems(OnEnter(GameState::Paused), freeze_world)
+        // quitting to the menu resumes too, the next track fades the old one out
+        .add_systems(OnExit(GameState::Paused), (unfreeze_world, resume_audio));
+    }
+}
+
+fn pause_pressed(
+    keyboard_input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_input: &Input<GamepadButton>,
+) -> bool {
+    keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Start))
+        })
+}
+
+fn pause_game(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if pause_pressed(&keyboard_input, &gamepads, &gamepad_input) {
+        next_state.set(GameState::Paused);
+    }
+}
+
+fn pause_on_focus_lost(
+    mut focus_events: EventReader<WindowFocused>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in focus_events.read() {
+        if !event.focused {
+            next_state.set(GameState::Paused);
+        }
+    }
+}
+
+fn resume_game(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if pause_pressed(&keyboard_input, &gamepads, &gamepad_input) {
+        next_state.set(GameState::Playing);
+    }
+}
+
+// Stops physics and virtual time, which also halts every timer,
+// sprite animation and particle that runs on `Time`. The ui channel keeps
+// playing for the pause menu.
+fn freeze_world(
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut time: ResMut<Time<Virtual>>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    stem_channel: Res<AudioChannel<StemChannel>>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+) {
+    rapier_config.physics_pipeline_active = false;
+    time.pause();
+    music_channel.pause();
+    stem_channel.pause();
+    sfx_channel.pause();
+}
+
+fn unfreeze_world(mut rapier_config: ResMut<RapierConfiguration>, mut time: ResMut<Time<Virtual>>) {
+    rapier_config.physics_pipeline_active = true;
+    time.unpause();
+}
+
+fn resume_audio(
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    stem_channel: Res<AudioChannel<StemChannel>>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+) {
+    music_channel.resume();
+    stem_channel.resume();
+    sfx_channel.resume();
+}
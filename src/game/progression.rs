@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// How much xp each level needs, loaded from `progression.ron`
+#[derive(Asset, TypePath, Deserialize)]
+pub struct ProgressionAsset {
+    pub curve: ProgressionCurve,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum ProgressionCurve {
+    // base + per_level * level
+    Linear {
+        base: u32,
+        per_level: u32,
+    },
+    // base + factor * level ^ exponent
+    Polynomial {
+        base: f32,
+        factor: f32,
+        exponent: f32,
+    },
+    // xp per level, the last entry is used for every level after the table
+    Table(Vec<u32>),
+}
+
+impl Default for ProgressionCurve {
+    fn default() -> Self {
+        Self::Linear {
+            base: 10,
+            per_level: 2,
+        }
+    }
+}
+
+impl ProgressionCurve {
+    /// The xp needed to advance from `level` to the next one, never zero
+    pub fn xp_for_level(&self, level: u32) -> u32 {
+        let xp = match self {
+            Self::Linear { base, per_level } => {
+                base.saturating_add(per_level.saturating_mul(level))
+            }
+            Self::Polynomial {
+                base,
+                factor,
+                exponent,
+            } => (base + factor * (level as f32).powf(*exponent)).round() as u32,
+            Self::Table(table) => table
+                .get(level as usize)
+                .or(table.last())
+                .copied()
+                .unwrap_or(1),
+        };
+
+        xp.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_grows_by_a_fixed_amount() {
+        let curve = ProgressionCurve::Linear {
+            base: 10,
+            per_level: 2,
+        };
+
+        assert_eq!(curve.xp_for_level(0), 10);
+        assert_eq!(curve.xp_for_level(1), 12);
+        assert_eq!(curve.xp_for_level(10), 30);
+    }
+
+    #[test]
+    fn polynomial_rounds_to_nearest() {
+        let curve = ProgressionCurve::Polynomial {
+            base: 5.0,
+            factor: 1.5,
+            exponent: 2.0,
+        };
+
+        assert_eq!(curve.xp_for_level(0), 5);
+        assert_eq!(curve.xp_for_level(1), 7);
+        assert_eq!(curve.xp_for_level(3), 19);
+    }
+
+    #[test]
+    fn table_repeats_last_entry() {
+        let curve = ProgressionCurve::Table(vec![3, 5, 8]);
+
+        assert_eq!(curve.xp_for_level(0), 3);
+        assert_eq!(curve.xp_for_level(2), 8);
+        assert_eq!(curve.xp_for_level(50), 8);
+    }
+
+    #[test]
+    fn never_needs_zero_xp() {
+        assert_eq!(ProgressionCurve::Table(vec![]).xp_for_level(0), 1);
+        assert_eq!(ProgressionCurve::Table(vec![0]).xp_for_level(4), 1);
+        assert_eq!(
+            ProgressionCurve::Polynomial {
+                base: 0.0,
+                factor: 0.0,
+                exponent: 1.0,
+            }
+            .xp_for_level(7),
+            1
+        );
+    }
+}
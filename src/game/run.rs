@@ -0,0 +1,174 @@
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+use super::{
+    components::{GameRules, GameState},
+    difficulty::Difficulty,
+    stats::RunStats,
+};
+
+pub struct RunPlugin;
+
+impl Plugin for RunPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnTransition {
+                from: GameState::Menu,
+                to: GameState::Playing,
+            },
+            start_run,
+        )
+        .add_systems(
+            OnTransition {
+                from: GameState::GameOver,
+                to: GameState::Playing,
+            },
+            start_run,
+        )
+        .add_systems(OnEnter(GameState::Menu), teardown_run);
+    }
+}
+
+/// Marks an entity that only lives for a single run. Every `RunScoped` entity
+/// is despawned with its children when the run ends or a new one starts.
+#[derive(Component)]
+pub struct RunScoped;
+
+/// Runs once when a fresh run starts, after the previous run was torn down.
+/// Resuming from the upgrade or pause screen does not start a new run.
+#[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnRunStart;
+
+fn start_run(world: &mut World) {
+    teardown_run(world);
+    world.try_run_schedule(OnRunStart).ok();
+}
+
+// The one place that knows what belongs to a run
+fn teardown_run(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<RunScoped>>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        // children are already gone if their parent was despawned first
+        if let Some(entity) = world.get_entity_mut(entity) {
+            entity.despawn_recursive();
+        }
+    }
+
+    world.resource_mut::<GameRules>().reset();
+    world.resource_mut::<Difficulty>().reset();
+    world.insert_resource(RunStats::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        camera::components::WorldCamera,
+        enemy::{
+            components::Enemy,
+            resources::EnemySpawnTimer,
+            systems::{setup_enemy_timer, spawn_enemies},
+        },
+        game::tuning::Tuning,
+        loading::resources::SpriteSheets,
+        player::{components::Player, systems::spawn_player},
+        ui::{components::TagGameUI, systems::setup_game_ui},
+        xp::{
+            components::{XPDropEvent, XP},
+            systems::spawn_xp,
+        },
+    };
+
+    // The spawners a run uses in the game, so one that forgets RunScoped fails
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_state::<GameState>()
+            .init_resource::<GameRules>()
+            .init_resource::<Difficulty>()
+            .init_resource::<RunStats>()
+            .init_resource::<Tuning>()
+            .insert_resource(SpriteSheets {
+                player: Handle::default(),
+                enemy: Handle::default(),
+            })
+            .add_event::<XPDropEvent>()
+            .add_plugins(RunPlugin)
+            .add_systems(OnRunStart, (spawn_player, setup_game_ui, setup_enemy_timer))
+            .add_systems(
+                Update,
+                (spawn_enemies, spawn_xp).run_if(in_state(GameState::Playing)),
+            );
+        app.world.spawn((
+            WorldCamera,
+            GlobalTransform::default(),
+            OrthographicProjection::default(),
+        ));
+        app
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world.resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    fn count<F: bevy::ecs::query::ReadOnlyWorldQuery>(app: &mut App) -> usize {
+        app.world.query_filtered::<(), F>().iter(&app.world).count()
+    }
+
+    fn play_and_die(app: &mut App) {
+        app.world.resource_mut::<GameRules>().add_xp(100);
+        app.world.resource_mut::<Difficulty>().update(120.0, 4);
+        app.world.resource_mut::<RunStats>().xp_collected = 100;
+        // an enemy every frame and an xp orb, like mid-run
+        app.world.resource_mut::<Difficulty>().spawn_interval = 0.0;
+        app.world.send_event(XPDropEvent {
+            amount: 1,
+            location: Vec3::ZERO,
+        });
+        app.update();
+        assert!(count::<With<Enemy>>(app) > 0);
+        assert_eq!(count::<With<XP>>(app), 1);
+        set_state(app, GameState::GameOver);
+    }
+
+    fn assert_fresh_run(app: &mut App) {
+        assert_eq!(count::<With<Player>>(app), 1);
+        assert_eq!(count::<With<TagGameUI>>(app), 1);
+        assert_eq!(count::<With<EnemySpawnTimer>>(app), 1);
+        assert_eq!(count::<With<Enemy>>(app), 0);
+        assert_eq!(count::<With<XP>>(app), 0);
+        assert_eq!(app.world.resource::<GameRules>().level, 0);
+        assert_eq!(app.world.resource::<GameRules>().xp, 0);
+        assert_eq!(app.world.resource::<Difficulty>().elapsed, 0.0);
+        assert_eq!(app.world.resource::<RunStats>().xp_collected, 0);
+    }
+
+    #[test]
+    fn back_to_back_runs_leave_a_clean_world() {
+        let mut app = app();
+        set_state(&mut app, GameState::Menu);
+        let empty_world = app.world.entities().len();
+
+        set_state(&mut app, GameState::Playing);
+        assert_fresh_run(&mut app);
+        play_and_die(&mut app);
+
+        // retry straight from the game over screen
+        set_state(&mut app, GameState::Playing);
+        assert_fresh_run(&mut app);
+        play_and_die(&mut app);
+
+        set_state(&mut app, GameState::Menu);
+        assert_eq!(count::<With<RunScoped>>(&mut app), 0);
+        assert_eq!(count::<With<Player>>(&mut app), 0);
+        assert_eq!(count::<With<Enemy>>(&mut app), 0);
+        assert_eq!(count::<With<TagGameUI>>(&mut app), 0);
+        assert_eq!(count::<With<EnemySpawnTimer>>(&mut app), 0);
+        assert_eq!(app.world.entities().len(), empty_world);
+        assert_eq!(app.world.resource::<GameRules>().level, 0);
+    }
+}
@@ -0,0 +1,69 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::components::GameState;
+use crate::{
+    enemy::components::{EnemyKilledEvent, EnemyKind},
+    player::components::{DamageDealtEvent, Weapon},
+    upgrade::components::UpgradeTakenEvent,
+    xp::components::XPCollectedEvent,
+};
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_systems(
+                Update,
+                track_time_survived.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (track_kills, track_damage, track_xp, track_upgrades),
+            );
+    }
+}
+
+/// Everything that happened during the current run, shown on the game over screen
+#[derive(Resource, Default)]
+pub struct RunStats {
+    pub time_survived: f32,
+    pub kills: HashMap<EnemyKind, u32>,
+    pub damage_dealt: HashMap<Weapon, f32>,
+    pub xp_collected: u32,
+    pub upgrades: Vec<String>,
+}
+
+impl RunStats {
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+}
+
+fn track_time_survived(mut stats: ResMut<RunStats>, time: Res<Time>) {
+    stats.time_survived += time.delta_seconds();
+}
+
+fn track_kills(mut stats: ResMut<RunStats>, mut events: EventReader<EnemyKilledEvent>) {
+    for event in events.read() {
+        *stats.kills.entry(event.kind).or_default() += 1;
+    }
+}
+
+fn track_damage(mut stats: ResMut<RunStats>, mut events: EventReader<DamageDealtEvent>) {
+    for event in events.read() {
+        *stats.damage_dealt.entry(event.weapon).or_default() += event.amount;
+    }
+}
+
+fn track_xp(mut stats: ResMut<RunStats>, mut events: EventReader<XPCollectedEvent>) {
+    for event in events.read() {
+        stats.xp_collected += event.amount;
+    }
+}
+
+fn track_upgrades(mut stats: ResMut<RunStats>, mut events: EventReader<UpgradeTakenEvent>) {
+    for event in events.read() {
+        stats.upgrades.push(event.name.clone());
+    }
+}
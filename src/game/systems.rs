@@ -1,11 +1,13 @@
 use crate::{
     audio::{
         components::{
-            MusicType, PlayMusicEvent, PlaySoundEffectEvent, SoundEffectType, StopSoundEvent,
+            MusicType, PlayMusicEvent, PlayStingerEvent, SoundChannel, StingerType, StopSoundEvent,
         },
         systems::AjmAudioPlugin,
     },
-    debug::fps::FPSPlugin,
+    debug::{difficulty::DifficultyDebugPlugin, fps::FPSPlugin},
+    loading::systems::LoadingPlugin,
+    settings::systems::SettingsPlugin,
     window::systems::CustomWindowPlugin,
 };
 
@@ -18,58 +20,104 @@ pub struct GameStatePlugin;
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
-            .add_plugins((CustomWindowPlugin, AudioPlugin, AjmAudioPlugin))
+            .add_plugins((
+                SettingsPlugin,
+                CustomWindowPlugin,
+                AudioPlugin,
+                AjmAudioPlugin,
+            ))
             .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
-            .add_plugins(GamePlugin)
-            .add_plugins((FrameTimeDiagnosticsPlugin, FPSPlugin));
+            .add_plugins((LoadingPlugin, GamePlugin))
+            .add_plugins((FrameTimeDiagnosticsPlugin, FPSPlugin, DifficultyDebugPlugin));
 
         // Menu Music
         app.add_systems(OnEnter(GameState::Menu), setup_state_menu);
 
-        // Game Music
-        app.add_systems(OnEnter(GameState::Playing), setup_state_playing);
+        // Game Music, not restarted when resuming from pause or the upgrade screen
+        app.add_systems(
+            OnTransition {
+                from: GameState::Menu,
+                to: GameState::Playing,
+            },
+            setup_state_playing,
+        )
+        .add_systems(
+            OnTransition {
+                from: GameState::Upgrade,
+                to: GameState::Playing,
+            },
+            resume_state_playing,
+        )
+        .add_systems(
+            OnTransition {
+                from: GameState::GameOver,
+                to: GameState::Playing,
+            },
+            setup_state_playing,
+        );
 
         // Upgade Music
         app.add_systems(OnEnter(GameState::Upgrade), setup_state_upgrade);
+
+        // Game Over stinger, over the ducked game music
+        app.add_systems(OnEnter(GameState::GameOver), setup_state_game_over);
     }
 }
 
-fn setup_state_menu(
-    mut sound_event: EventWriter<PlayMusicEvent>,
-    audio: Res<Audio>,
-    mut game: ResMut<GameRules>,
-) {
-    audio.stop();
-    sound_event.send(PlayMusicEvent {
+// Every track crossfades into the next, sound effects keep playing
+fn setup_state_menu(mut music_event: EventWriter<PlayMusicEvent>) {
+    music_event.send(PlayMusicEvent {
         sound: MusicType::Menu,
         looping: true,
-        fade_in: 1000,
+        ..default()
     });
+}
 
-    game.reset();
+// how long the music of the menu or the last run takes to fade out
+const NEW_RUN_FADE_OUT: u64 = 500;
+
+// a new run starts the game track over, whatever was playing before is stopped
+// instead of crossfaded so a retry does not keep the last run's track
+fn setup_state_playing(
+    mut music_event: EventWriter<PlayMusicEvent>,
+    mut stop_event: EventWriter<StopSoundEvent>,
+) {
+    stop_event.send(StopSoundEvent {
+        channel: SoundChannel::Music,
+        fade_out: NEW_RUN_FADE_OUT,
+    });
+    music_event.send(PlayMusicEvent {
+        sound: MusicType::Game,
+        looping: true,
+        ..default()
+    });
 }
 
-fn setup_state_playing(mut sound_event: EventWriter<PlayMusicEvent>, audio: Res<Audio>) {
-    audio.stop();
-    sound_event.send(PlayMusicEvent {
+fn resume_state_playing(mut music_event: EventWriter<PlayMusicEvent>) {
+    music_event.send(PlayMusicEvent {
         sound: MusicType::Game,
         looping: true,
+        resume: true,
         ..default()
     });
 }
 
 fn setup_state_upgrade(
     mut music_event: EventWriter<PlayMusicEvent>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
-    audio: Res<Audio>,
+    mut stinger_event: EventWriter<PlayStingerEvent>,
 ) {
-    audio.stop();
-    sound_event.send(PlaySoundEffectEvent {
-        sound: SoundEffectType::EnterLevelUp,
+    stinger_event.send(PlayStingerEvent {
+        stinger: StingerType::LevelUp,
     });
     music_event.send(PlayMusicEvent {
         sound: MusicType::Upgrade,
         looping: true,
-        fade_in: 1000,
+        ..default()
+    });
+}
+
+fn setup_state_game_over(mut stinger_event: EventWriter<PlayStingerEvent>) {
+    stinger_event.send(PlayStingerEvent {
+        stinger: StingerType::Death,
     });
 }
@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::difficulty::{Difficulty, DifficultyConfig};
+use crate::base::loader::RonAssetPlugin;
+
+pub struct TuningPlugin;
+
+#[derive(Resource)]
+struct TuningHandle(Handle<Tuning>);
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(RonAssetPlugin::<Tuning>::new(&["tuning.ron"]))
+            .init_resource::<Tuning>()
+            .add_systems(Startup, load_tuning)
+            .add_systems(Update, apply_tuning);
+    }
+}
+
+/// Gameplay numbers from `tuning.ron`. Saving the file while the game runs
+/// swaps the resource, systems read it every time they need a value.
+#[derive(Asset, TypePath, Resource, Deserialize, Clone)]
+#[serde(default)]
+pub struct Tuning {
+    pub player: PlayerTuning,
+    pub enemy: EnemyTuning,
+    // seconds between getting hit and losing health, the hit flash lasts as long
+    pub hurt_duration: f32,
+    // how far outside the screen enemies spawn
+    pub spawn_padding: f32,
+    pub particles: ParticleTuning,
+    pub difficulty: DifficultyConfig,
+    pub music: MusicTuning,
+    pub juice: JuiceTuning,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            player: PlayerTuning::default(),
+            enemy: EnemyTuning::default(),
+            hurt_duration: 0.1,
+            spawn_padding: 50.0,
+            particles: ParticleTuning::default(),
+            difficulty: DifficultyConfig::default(),
+            music: MusicTuning::default(),
+            juice: JuiceTuning::default(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PlayerTuning {
+    // sprite scale in world units per art pixel, 5.0 is one virtual pixel
+    pub size: f32,
+    pub health: f32,
+    pub move_speed: f32,
+    // seconds between shots
+    pub shot_speed: f32,
+    pub bullet_speed: f32,
+    pub bullet_damage: f32,
+    // seconds before a bullet that hit nothing disappears
+    pub bullet_lifetime: f32,
+    // xp is pulled in once its own 5 unit sensor touches this radius
+    pub pickup_radius: f32,
+    pub pickup_strength: f32,
+}
+
+impl Default for PlayerTuning {
+    fn default() -> Self {
+        Self {
+            size: 5.0,
+            health: 3.0,
+            move_speed: 300.0,
+            shot_speed: 0.4,
+            bullet_speed: 500.0,
+            bullet_damage: 1.0,
+            bullet_lifetime: 3.0,
+            pickup_radius: 195.0,
+            pickup_strength: 5.0,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct EnemyTuning {
+    // sprite scale like the player's
+    pub size: f32,
+    pub health: f32,
+    pub move_speed: f32,
+    pub damage: f32,
+    // distance to the player at which the attack animation starts
+    pub attack_range: f32,
+}
+
+impl Default for EnemyTuning {
+    fn default() -> Self {
+        Self {
+            size: 5.0,
+            health: 3.0,
+            move_speed: 100.0,
+            damage: 1.0,
+            attack_range: 60.0,
+        }
+    }
+}
+
+/// Blood particles spawned when an enemy is hit
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct ParticleTuning {
+    pub count_min: u32,
+    pub count_max: u32,
+    pub size: f32,
+    pub speed: f32,
+    // how far particles fan out sideways from the hit direction
+    pub spread: f32,
+    pub lifetime_min: f32,
+    pub lifetime_max: f32,
+    pub gravity: f32,
+    // how far below their start particles bounce, and how much speed they keep
+    pub ground: f32,
+    pub bounce: f32,
+}
+
+impl Default for ParticleTuning {
+    fn default() -> Self {
+        Self {
+            count_min: 5,
+            count_max: 10,
+            size: 3.0,
+            speed: 0.05,
+            spread: 3.0,
+            lifetime_min: 0.3,
+            lifetime_max: 0.8,
+            gravity: -98.1,
+            ground: 50.0,
+            bounce: 0.7,
+        }
+    }
+}
+
+/// How combat intensity is measured and how the game music stems follow it
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct MusicTuning {
+    // enemies on screen that count as a full crowd
+    pub crowd_size: f32,
+    // how much a full crowd and an almost dead player add to intensity
+    pub crowd_weight: f32,
+    pub danger_weight: f32,
+    // intensity per second, rising quickly and settling slowly
+    pub rise_speed: f32,
+    pub fall_speed: f32,
+    // intensity range over which each stem fades in
+    pub drums: (f32, f32),
+    pub bass: (f32, f32),
+    pub lead: (f32, f32),
+}
+
+impl Default for MusicTuning {
+    fn default() -> Self {
+        Self {
+            crowd_size: 30.0,
+            crowd_weight: 0.7,
+            danger_weight: 0.4,
+            rise_speed: 1.0,
+            fall_speed: 0.1,
+            drums: (0.1, 0.3),
+            bass: (0.3, 0.55),
+            lead: (0.6, 0.85),
+        }
+    }
+}
+
+/// Screen shake, hit-stop and flashes, and what sets them off
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct JuiceTuning {
+    // camera offset and rotation in radians at full trauma
+    pub shake_offset: f32,
+    pub shake_angle: f32,
+    // how fast the shake wobbles, and trauma lost per second
+    pub shake_frequency: f32,
+    pub trauma_decay: f32,
+    // game speed while a hit-stop lasts
+    pub hit_stop_speed: f32,
+    pub flash_alpha: f32,
+    pub flash_duration: f32,
+    pub hurt_trauma: f32,
+    pub hurt_hit_stop: f32,
+    pub kill_hit_stop: f32,
+    pub elite_kill_trauma: f32,
+    pub elite_kill_hit_stop: f32,
+}
+
+impl Default for JuiceTuning {
+    fn default() -> Self {
+        Self {
+            shake_offset: 12.0,
+            shake_angle: 0.05,
+            shake_frequency: 25.0,
+            trauma_decay: 1.5,
+            hit_stop_speed: 0.05,
+            flash_alpha: 0.35,
+            flash_duration: 0.15,
+            hurt_trauma: 0.5,
+            hurt_hit_stop: 0.08,
+            kill_hit_stop: 0.02,
+            elite_kill_trauma: 0.3,
+            elite_kill_hit_stop: 0.06,
+        }
+    }
+}
+
+fn load_tuning(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuningHandle(asset_server.load("tuning.ron")));
+}
+
+fn apply_tuning(
+    mut asset_events: EventReader<AssetEvent<Tuning>>,
+    tunings: Res<Assets<Tuning>>,
+    handle: Res<TuningHandle>,
+    mut tuning: ResMut<Tuning>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    for event in asset_events.read() {
+        if event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0) {
+            if let Some(loaded) = tunings.get(&handle.0) {
+                println!("Applying tuning.ron");
+                *tuning = loaded.clone();
+                difficulty.config = loaded.difficulty.clone();
+            }
+        }
+    }
+}
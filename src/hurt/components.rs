@@ -6,4 +6,58 @@ pub struct Hurting(pub f32);
 #[derive(Component)]
 pub struct Damage(pub f32);
 
-pub const HURT_DURATION: f32 = 0.1;
+pub const SQUASH_DURATION: f32 = 0.2;
+// how far the sprite is squashed at the start of a hit
+pub const SQUASH_AMOUNT: f32 = 0.3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tint {
+    Elite,
+}
+
+impl Tint {
+    pub fn color(&self) -> Color {
+        match self {
+            Tint::Elite => Color::rgb(1.0, 0.8, 0.3),
+        }
+    }
+}
+
+/// Colour layers on a sprite, multiplied together so none of them wins over the
+/// others. Status effects like freeze or poison add their own `Tint` when they exist.
+#[derive(Component, Default)]
+pub struct TintStack {
+    layers: Vec<Tint>,
+}
+
+impl TintStack {
+    pub fn new(layers: &[Tint]) -> Self {
+        Self {
+            layers: layers.to_vec(),
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        self.layers.iter().fold(Color::WHITE, |color, tint| {
+            let tint = tint.color();
+            Color::rgba(
+                color.r() * tint.r(),
+                color.g() * tint.g(),
+                color.b() * tint.b(),
+                color.a() * tint.a(),
+            )
+        })
+    }
+}
+
+/// White silhouette drawn over a sprite while its character is hurt. A tint can
+/// only darken the texture, so the flash is its own sprite on a white atlas.
+#[derive(Component)]
+pub struct HitFlash;
+
+/// Squash and stretch on a sprite after a hit, easing back to `base`
+#[derive(Component)]
+pub struct Squash {
+    pub timer: Timer,
+    pub base: Vec3,
+}
@@ -0,0 +1,101 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use super::components::Hurting;
+use crate::{
+    camera::pixel::WORLD_UNITS_PER_PIXEL,
+    enemy::components::Enemy,
+    game::{components::GameState, run::RunScoped},
+    settings::resources::Settings,
+};
+
+const DAMAGE_NUMBER_DURATION: f32 = 0.6;
+// in virtual pixels, the text is scaled up to world units like the sprites
+const DAMAGE_NUMBER_FONT_SIZE: f32 = 8.0;
+const DAMAGE_NUMBER_RISE: f32 = 12.0 * WORLD_UNITS_PER_PIXEL;
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            spawn_damage_numbers.run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(Update, update_damage_numbers);
+    }
+}
+
+#[derive(Component)]
+struct DamageNumber(Timer);
+
+// a number over every enemy hit, each one of a quick burst gets its own
+fn spawn_damage_numbers(
+    mut commands: Commands,
+    query: Query<(&Hurting, &GlobalTransform), (With<Enemy>, Changed<Hurting>)>,
+    settings: Res<Settings>,
+) {
+    if !settings.gameplay.damage_numbers {
+        return;
+    }
+
+    for (hurting, transform) in &query {
+        let position = transform.translation().truncate();
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    format_damage(hurting.0),
+                    TextStyle {
+                        font_size: DAMAGE_NUMBER_FONT_SIZE,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                text_anchor: Anchor::BottomCenter,
+                // above the enemies and their hit flash
+                transform: Transform::from_translation(position.extend(10.0))
+                    .with_scale(Vec3::splat(WORLD_UNITS_PER_PIXEL)),
+                ..default()
+            },
+            DamageNumber(Timer::from_seconds(DAMAGE_NUMBER_DURATION, TimerMode::Once)),
+            RunScoped,
+        ));
+    }
+}
+
+// rises and fades out in any state, like the other hit reactions
+fn update_damage_numbers(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut DamageNumber, &mut Transform, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut number, mut transform, mut text) in &mut query {
+        number.0.tick(time.delta());
+        if number.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation.y += DAMAGE_NUMBER_RISE * time.delta_seconds();
+        let alpha = 1.0 - number.0.percent();
+        for section in &mut text.sections {
+            section.style.color.set_a(alpha);
+        }
+    }
+}
+
+// whole numbers without a decimal point, upgraded damage to one decimal
+fn format_damage(damage: f32) -> String {
+    format!("{}", (damage * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damage_is_shown_to_one_decimal() {
+        assert_eq!(format_damage(1.0), "1");
+        assert_eq!(format_damage(1.5), "1.5");
+        assert_eq!(format_damage(2.0 / 3.0), "0.7");
+    }
+}
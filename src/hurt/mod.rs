@@ -1,3 +1,4 @@
 pub mod components;
+pub mod damage_numbers;
 pub mod resources;
 pub mod systems;
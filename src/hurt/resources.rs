@@ -1,4 +1,70 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, render::render_resource::TextureFormat, utils::HashMap};
 
 #[derive(Component, Deref, DerefMut)]
 pub struct HurtTimer(pub Timer);
+
+/// White copies of every atlas a hit flash was drawn for, made on the first hit
+#[derive(Resource, Default)]
+pub struct FlashAtlases(HashMap<AssetId<TextureAtlas>, Handle<TextureAtlas>>);
+
+impl FlashAtlases {
+    pub fn get_or_create(
+        &mut self,
+        atlas: &Handle<TextureAtlas>,
+        atlases: &mut Assets<TextureAtlas>,
+        images: &mut Assets<Image>,
+    ) -> Option<Handle<TextureAtlas>> {
+        if let Some(flash) = self.0.get(&atlas.id()) {
+            return Some(flash.clone());
+        }
+
+        let source = atlases.get(atlas)?;
+        let image = images.get(&source.texture)?;
+        let white = white_silhouette(image)?;
+
+        let mut flash = source.clone();
+        flash.texture = images.add(white);
+        let flash = atlases.add(flash);
+        self.0.insert(atlas.id(), flash.clone());
+        Some(flash)
+    }
+}
+
+// every visible pixel turned white, the alpha is kept
+fn white_silhouette(image: &Image) -> Option<Image> {
+    if !matches!(
+        image.texture_descriptor.format,
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm
+    ) {
+        return None;
+    }
+
+    let mut white = image.clone();
+    for pixel in white.data.chunks_exact_mut(4) {
+        pixel[..3].fill(255);
+    }
+    Some(white)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::render::render_resource::{Extent3d, TextureDimension};
+
+    #[test]
+    fn silhouette_keeps_the_shape_and_drops_the_colour() {
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![0, 0, 0, 255, 40, 80, 120, 0],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+
+        let white = white_silhouette(&image).unwrap();
+        assert_eq!(white.data, vec![255, 255, 255, 255, 255, 255, 255, 0]);
+    }
+}
@@ -1,10 +1,13 @@
+use crate::animation::{components::*, systems::AnimationSet};
 use crate::damagable::components::Damageable;
-use crate::game::components::GameState;
+use crate::game::{components::GameState, tuning::Tuning};
 use crate::health::components::Health;
 
 use super::components::*;
+use super::damage_numbers::DamageNumbersPlugin;
 use super::resources::*;
 use bevy::prelude::*;
+use bevy_asepritesheet::animator::animate_sprites;
 
 pub struct HurtPlugin;
 
@@ -14,7 +17,7 @@ impl Plugin for HurtPlugin {
             Update,
             (
                 start_hurt,
-                flash_sprite_red,
+                start_hit_reaction.in_set(AnimationSet),
                 tick_hurt_timer,
                 apply_damage,
                 stop_hurt,
@@ -22,45 +25,161 @@ impl Plugin for HurtPlugin {
                 .chain()
                 .run_if(in_state(GameState::Playing)),
         );
+
+        // reactions finish playing out in any state
+        app.add_plugins(DamageNumbersPlugin)
+            .init_resource::<FlashAtlases>()
+            .add_systems(Update, (stop_hit_flash, update_squash, apply_tints).chain())
+            .add_systems(PostUpdate, follow_hit_flash.after(animate_sprites));
     }
 }
 
-fn flash_sprite_red(
-    mut query: Query<(Option<&Hurting>, &Children)>,
-    mut sprite_query: Query<&mut TextureAtlasSprite>,
+// Plays the hurt animation, flashes and squashes every sprite of a character
+// that just got hit, again on every hit that lands while it is still hurting
+#[allow(clippy::type_complexity)]
+fn start_hit_reaction(
+    mut commands: Commands,
+    query: Query<&Children, Changed<Hurting>>,
+    mut sprite_query: Query<(
+        Entity,
+        &Transform,
+        Option<&mut AnimationController>,
+        Option<&mut Squash>,
+        Option<(&TextureAtlasSprite, &Handle<TextureAtlas>)>,
+        Option<&Children>,
+    )>,
+    flash_query: Query<(), With<HitFlash>>,
+    mut flash_atlases: ResMut<FlashAtlases>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+    mut images: ResMut<Assets<Image>>,
 ) {
-    for (hurting, children) in query.iter_mut() {
-        match hurting {
-            Some(_) => {
-                for child in children {
-                    if let Ok(mut sprite) = sprite_query.get_mut(*child) {
-                        sprite.color = Color::RED;
-                    }
+    for children in &query {
+        for child in children {
+            let Ok((entity, transform, controller, squash, sprite, sprite_children)) =
+                sprite_query.get_mut(*child)
+            else {
+                continue;
+            };
+
+            let flashing = sprite_children
+                .is_some_and(|children| children.iter().any(|c| flash_query.contains(*c)));
+            if let (Some((sprite, atlas)), false) = (sprite, flashing) {
+                if let Some(flash) = flash_atlases.get_or_create(atlas, &mut atlases, &mut images) {
+                    commands.entity(entity).with_children(|parent| {
+                        parent.spawn((
+                            SpriteSheetBundle {
+                                sprite: TextureAtlasSprite {
+                                    color: Color::WHITE,
+                                    ..sprite.clone()
+                                },
+                                texture_atlas: flash,
+                                // just in front of the sprite it covers
+                                transform: Transform::from_xyz(0.0, 0.0, 0.01),
+                                ..default()
+                            },
+                            HitFlash,
+                        ));
+                    });
                 }
             }
-            None => {
-                for child in children.iter() {
-                    if let Ok(mut sprite) = sprite_query.get_mut(*child) {
-                        sprite.color = Color::WHITE;
-                    }
+
+            if let Some(mut controller) = controller {
+                controller.play_once(AnimState::Hurt);
+            }
+
+            match squash {
+                Some(mut squash) => squash.timer.reset(),
+                None => {
+                    commands.entity(entity).insert(Squash {
+                        timer: Timer::from_seconds(SQUASH_DURATION, TimerMode::Once),
+                        base: transform.scale,
+                    });
                 }
             }
         }
     }
 }
 
+// the flash lasts as long as the hurt itself
+fn stop_hit_flash(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Hurting>,
+    query: Query<&Children>,
+    flash_query: Query<(), With<HitFlash>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = query.get(entity) else {
+            continue;
+        };
+        for sprite in children {
+            let Ok(sprite_children) = query.get(*sprite) else {
+                continue;
+            };
+            for flash in sprite_children {
+                if flash_query.contains(*flash) {
+                    commands.entity(*flash).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+// keeps the flash on the frame and facing of the sprite under it
+fn follow_hit_flash(
+    mut flash_query: Query<(&Parent, &mut TextureAtlasSprite), With<HitFlash>>,
+    sprite_query: Query<&TextureAtlasSprite, Without<HitFlash>>,
+) {
+    for (parent, mut flash) in &mut flash_query {
+        let Ok(sprite) = sprite_query.get(parent.get()) else {
+            continue;
+        };
+        flash.index = sprite.index;
+        flash.flip_x = sprite.flip_x;
+        flash.flip_y = sprite.flip_y;
+        flash.anchor = sprite.anchor;
+        flash.custom_size = sprite.custom_size;
+    }
+}
+
+fn update_squash(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Squash, &mut Transform)>,
+    time: Res<Time>,
+) {
+    for (entity, mut squash, mut transform) in &mut query {
+        squash.timer.tick(time.delta());
+
+        if squash.timer.finished() {
+            transform.scale = squash.base;
+            commands.entity(entity).remove::<Squash>();
+            continue;
+        }
+
+        // wide and flat on impact, springing back through a stretch
+        let t = squash.timer.percent();
+        let amount = SQUASH_AMOUNT * (1.0 - t) * (t * std::f32::consts::TAU).cos();
+        transform.scale = squash.base * Vec3::new(1.0 + amount, 1.0 - amount, 1.0);
+    }
+}
+
+fn apply_tints(mut query: Query<(&TintStack, &mut TextureAtlasSprite), Changed<TintStack>>) {
+    for (tints, mut sprite) in &mut query {
+        sprite.color = tints.color();
+    }
+}
+
 fn tick_hurt_timer(mut query: Query<&mut HurtTimer>, time: Res<Time>) {
     for mut timer in query.iter_mut() {
         timer.0.tick(time.delta());
     }
 }
 
-fn start_hurt(mut commands: Commands, query: Query<Entity, Added<Hurting>>) {
+fn start_hurt(mut commands: Commands, query: Query<Entity, Added<Hurting>>, tuning: Res<Tuning>) {
     for entity in query.iter() {
         commands
             .entity(entity)
             .insert(HurtTimer(Timer::from_seconds(
-                HURT_DURATION,
+                tuning.hurt_duration,
                 TimerMode::Once,
             )));
     }
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use bevy_asepritesheet::prelude::*;
+use bevy_asset_loader::prelude::*;
+
+use super::systems::{ENEMY_SHEET, PLAYER_SHEET};
+
+#[derive(AssetCollection, Resource)]
+pub struct TextureAssets {
+    #[asset(texture_atlas(
+        tile_size_x = 16.,
+        tile_size_y = 16.,
+        columns = 20,
+        rows = 20,
+        padding_x = 1.,
+        padding_y = 1.
+    ))]
+    #[asset(path = "monochrome_tilemap_transparent.png")]
+    pub tilemap: Handle<TextureAtlas>,
+}
+
+// The images behind the exported sheets, so they are ready before the sheets are built.
+// Sheets loaded from .aseprite files carry their own image.
+#[derive(AssetCollection, Resource)]
+pub struct SpriteImageAssets {
+    #[asset(path = "npc_4.png")]
+    pub npc_4: Handle<Image>,
+}
+
+#[derive(Resource)]
+pub struct SpriteSheets {
+    pub player: Handle<Spritesheet>,
+    pub enemy: Handle<Spritesheet>,
+}
+
+impl SpriteSheets {
+    /// The file a sheet was loaded from. The player sheet is built from its json
+    /// at runtime, so the asset server does not know its path.
+    pub fn path(&self, id: AssetId<Spritesheet>) -> Option<&'static str> {
+        if id == self.player.id() {
+            Some(PLAYER_SHEET)
+        } else if id == self.enemy.id() {
+            Some(ENEMY_SHEET)
+        } else {
+            None
+        }
+    }
+}
+
+/// Handles the loading screen waits on, split by whether the game can run without them
+#[derive(Resource, Default)]
+pub struct TrackedAssets {
+    pub critical: Vec<UntypedHandle>,
+    pub optional: Vec<UntypedHandle>,
+}
+
+/// Paths of every asset that failed to load, shown on the asset error screen
+#[derive(Resource, Default)]
+pub struct FailedAssets {
+    pub critical: Vec<String>,
+    pub optional: Vec<String>,
+}
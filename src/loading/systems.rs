@@ -0,0 +1,253 @@
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    sprite::Anchor,
+};
+use bevy_asepritesheet::prelude::*;
+use bevy_asset_loader::prelude::*;
+use iyes_progress::prelude::*;
+
+use super::resources::*;
+use crate::{
+    audio::{
+        components::GameAudioAssets,
+        sound_effects::{apply_sound_effects, SoundEffectLibrary, SoundEffectsHandle},
+    },
+    base::aseprite::AsepriteLoaderPlugin,
+    game::components::GameState,
+};
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            AsepritesheetPlugin::new(&["sprite.json"]),
+            AsepriteLoaderPlugin,
+        ));
+
+        app.add_plugins(ProgressPlugin::new(GameState::Loading).continue_to(GameState::Splash))
+            .init_resource::<TrackedAssets>()
+            .init_resource::<FailedAssets>()
+            .add_loading_state(
+                LoadingState::new(GameState::Loading)
+                    .on_failure_continue_to_state(GameState::AssetError)
+                    .load_collection::<SpriteImageAssets>(),
+            )
+            .add_systems(
+                OnEnter(GameState::Loading),
+                (load_tracked_assets, load_spritesheets),
+            )
+            .add_systems(
+                Update,
+                (
+                    track_critical_assets.track_progress(),
+                    track_sound_effects
+                        .after(apply_sound_effects)
+                        .before(track_optional_assets),
+                    track_optional_assets.track_progress(),
+                )
+                    .run_if(in_state(GameState::Loading)),
+            )
+            .add_systems(Update, configure_spritesheets);
+    }
+}
+
+// Audio and textures are optional, the game runs without them. A failed one is
+// reported and replaced by silence or a placeholder instead of holding up loading
+fn load_tracked_assets(world: &mut World) {
+    // same handles the loading state holds, tracked here to know which one failed
+    let critical = SpriteImageAssets::load(world);
+
+    let mut optional = GameAudioAssets::load(world);
+    optional.extend(TextureAssets::load(world));
+    // the sound effect files themselves are tracked once this is read
+    if let Some(sound_effects) = world.get_resource::<SoundEffectsHandle>() {
+        optional.push(sound_effects.0.clone().untyped());
+    }
+
+    let audio_assets = GameAudioAssets::create(world);
+    let texture_assets = TextureAssets::create(world);
+    world.insert_resource(audio_assets);
+    world.insert_resource(texture_assets);
+
+    let mut tracked = world.resource_mut::<TrackedAssets>();
+    tracked.critical.extend(critical);
+    tracked.optional = optional;
+}
+
+// Sound effect files are only known once sound_effects.ron is loaded
+fn track_sound_effects(library: Res<SoundEffectLibrary>, mut tracked: ResMut<TrackedAssets>) {
+    if !library.is_changed() {
+        return;
+    }
+
+    for handle in library.handles() {
+        let handle = handle.clone().untyped();
+        if !tracked.optional.contains(&handle) {
+            tracked.optional.push(handle);
+        }
+    }
+}
+
+// Sprite sheets the game loads, the validator checks these for their animation tags
+pub const PLAYER_SHEET: &str = "npc_4.sprite.json";
+pub const ENEMY_SHEET: &str = "enemy_3.aseprite";
+
+fn load_spritesheets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut tracked: ResMut<TrackedAssets>,
+) {
+    let player = load_spritesheet_then(
+        &mut commands,
+        &asset_server,
+        PLAYER_SHEET,
+        Anchor::Center,
+        hold_one_shots,
+    );
+
+    // straight from the source file, saving it in Aseprite hot reloads the enemy
+    let enemy = asset_server.load(ENEMY_SHEET);
+
+    // the player sheet is built empty and filled in once its json loads, so a
+    // missing or broken json only shows up on the json itself
+    let player_data: Handle<SpritesheetData> = asset_server.load(PLAYER_SHEET);
+    tracked
+        .critical
+        .extend([player_data.untyped(), enemy.clone().untyped()]);
+
+    commands.insert_resource(SpriteSheets { player, enemy });
+}
+
+const ONE_SHOT_TAGS: [&str; 2] = ["hurt", "death"];
+
+// Death stays on its last frame, hurt holds it until the animation controller
+// hands back to the previous state
+fn hold_one_shots(sheet: &mut Spritesheet) {
+    for name in ONE_SHOT_TAGS {
+        let handle = sheet.get_anim_handle(name);
+
+        if let Ok(anim) = sheet.get_anim_mut(&handle) {
+            anim.end_action = AnimEndAction::Pause;
+        }
+    }
+}
+
+fn holds_one_shots(sheet: &Spritesheet) -> bool {
+    ONE_SHOT_TAGS.iter().all(|name| {
+        sheet
+            .get_anim(&sheet.get_anim_handle(name))
+            .map_or(true, |anim| matches!(anim.end_action, AnimEndAction::Pause))
+    })
+}
+
+// Sheets loaded from .aseprite files, and any sheet after a hot reload, come
+// in without the end actions set above
+fn configure_spritesheets(
+    mut events: EventReader<AssetEvent<Spritesheet>>,
+    mut spritesheets: ResMut<Assets<Spritesheet>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::Added { id } | AssetEvent::Modified { id }) = event else {
+            continue;
+        };
+
+        // only touch sheets that need it, changing one sends another Modified event
+        if spritesheets
+            .get(*id)
+            .is_some_and(|sheet| !holds_one_shots(sheet))
+        {
+            if let Some(sheet) = spritesheets.get_mut(*id) {
+                hold_one_shots(sheet);
+            }
+        }
+    }
+}
+
+fn asset_path(asset_server: &AssetServer, handle: &UntypedHandle) -> String {
+    asset_server
+        .get_path(handle.id())
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| format!("{:?}", handle.id()))
+}
+
+fn track_critical_assets(
+    asset_server: Res<AssetServer>,
+    tracked: Res<TrackedAssets>,
+    mut failed: ResMut<FailedAssets>,
+    mut next_state: ResMut<NextState<GameState>>,
+) -> Progress {
+    let mut done = 0;
+    for handle in &tracked.critical {
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Loaded) => done += 1,
+            Some(LoadState::Failed) => {
+                let path = asset_path(&asset_server, handle);
+                if !failed.critical.contains(&path) {
+                    error!("Failed to load critical asset: {}", path);
+                    failed.critical.push(path);
+                }
+                next_state.set(GameState::AssetError);
+            }
+            _ => {}
+        }
+    }
+
+    Progress {
+        done,
+        total: tracked.critical.len() as u32,
+    }
+}
+
+fn track_optional_assets(
+    asset_server: Res<AssetServer>,
+    tracked: Res<TrackedAssets>,
+    mut failed: ResMut<FailedAssets>,
+    mut images: ResMut<Assets<Image>>,
+) -> Progress {
+    let mut done = 0;
+    for handle in &tracked.optional {
+        match asset_server.get_load_state(handle.id()) {
+            Some(LoadState::Loaded) => done += 1,
+            Some(LoadState::Failed) => {
+                done += 1;
+
+                let path = asset_path(&asset_server, handle);
+                if failed.optional.contains(&path) {
+                    continue;
+                }
+                warn!("Failed to load {}, continuing without it", path);
+                failed.optional.push(path);
+
+                if let Ok(id) = handle.id().try_typed::<Image>() {
+                    images.insert(id, placeholder_image());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Progress {
+        done,
+        total: tracked.optional.len() as u32,
+    }
+}
+
+// magenta and black checkerboard, impossible to miss in game
+fn placeholder_image() -> Image {
+    let magenta = [255, 0, 255, 255];
+    let black = [0, 0, 0, 255];
+
+    Image::new(
+        Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        [magenta, black, black, magenta].concat(),
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
@@ -0,0 +1,230 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bevy::{asset::io::file::FileAssetReader, prelude::*};
+use bevy_asset_loader::prelude::*;
+use bevy_kira_audio::AudioSource;
+use serde::Deserialize;
+
+use super::{
+    resources::{SpriteImageAssets, TextureAssets},
+    systems::{ENEMY_SHEET, PLAYER_SHEET},
+};
+use crate::{
+    animation::components::AnimState,
+    audio::{components::GameAudioAssets, sound_effects::SoundEffects},
+    base::aseprite::parse_aseprite,
+    enemy::components::ENEMY_ANIMATIONS,
+    game::{progression::ProgressionAsset, tuning::Tuning},
+    player::components::PLAYER_ANIMATIONS,
+};
+
+// Sprite sheets the game loads and the tags their animation controller maps to
+const SHEETS: &[(&str, &[(AnimState, &str)])] = &[
+    (PLAYER_SHEET, PLAYER_ANIMATIONS),
+    (ENEMY_SHEET, ENEMY_ANIMATIONS),
+];
+
+// RON data files and a check that they deserialize into the type the game expects
+const DATA_FILES: &[(&str, fn(&str) -> Result<(), String>)] = &[
+    ("progression.ron", parse_ron::<ProgressionAsset>),
+    ("tuning.ron", parse_ron::<Tuning>),
+    ("sound_effects.ron", parse_ron::<SoundEffects>),
+];
+
+fn parse_ron<T: for<'de> Deserialize<'de>>(contents: &str) -> Result<(), String> {
+    ron::from_str::<T>(contents)
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+/// Checks every asset the game references without starting it, run with
+/// `cargo run -- --validate-assets`. Returns the process exit code.
+pub fn run() -> i32 {
+    // the same folder the game loads from
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    // asset path -> what references it
+    let mut collection_paths = collection_paths();
+
+    // audio files and sprite sheets listed in the sound effect table
+    if let Ok(contents) = fs::read_to_string(assets.join("sound_effects.ron")) {
+        if let Ok(sound_effects) = ron::from_str::<SoundEffects>(&contents) {
+            for config in sound_effects.effects.values() {
+                for file in &config.files {
+                    collection_paths.insert(file.clone(), "sound_effects.ron".to_string());
+                }
+            }
+            for sheet in sound_effects.animation_frames.keys() {
+                collection_paths.insert(sheet.clone(), "sound_effects.ron".to_string());
+            }
+        }
+    }
+
+    println!("Validating assets in {}", assets.display());
+    let mut problems = Vec::new();
+
+    for (path, source) in &collection_paths {
+        if !assets.join(path).is_file() {
+            problems.push(format!("{}: missing, referenced in {}", path, source));
+        }
+    }
+
+    for (path, animations) in SHEETS {
+        if let Err(problem) = check_sheet(&assets, path, animations) {
+            problems.push(format!("{}: {}", path, problem));
+        }
+    }
+
+    for (path, check) in DATA_FILES {
+        let result = fs::read_to_string(assets.join(path))
+            .map_err(|error| error.to_string())
+            .and_then(|contents| check(&contents));
+        if let Err(problem) = result {
+            problems.push(format!("{}: {}", path, problem));
+        }
+    }
+
+    let checked = collection_paths.len() + SHEETS.len() + DATA_FILES.len();
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+
+    if problems.is_empty() {
+        println!("{} assets ok", checked);
+        0
+    } else {
+        println!("{} of {} assets have problems", problems.len(), checked);
+        1
+    }
+}
+
+// Every path the asset collections load, by collection name. The collections
+// only hand out handles, so they are loaded into an asset server that never
+// runs and the paths are read back from it.
+fn collection_paths() -> BTreeMap<String, String> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlas>()
+        .init_asset::<AudioSource>();
+
+    let mut paths = BTreeMap::new();
+    add_collection::<GameAudioAssets>(&mut app.world, "GameAudioAssets", &mut paths);
+    add_collection::<TextureAssets>(&mut app.world, "TextureAssets", &mut paths);
+    add_collection::<SpriteImageAssets>(&mut app.world, "SpriteImageAssets", &mut paths);
+    paths
+}
+
+fn add_collection<T: AssetCollection>(
+    world: &mut World,
+    name: &str,
+    paths: &mut BTreeMap<String, String>,
+) {
+    let handles = T::load(world);
+    let asset_server = world.resource::<AssetServer>();
+    for handle in handles {
+        if let Some(path) = asset_server.get_path(handle.id()) {
+            paths.insert(path.to_string(), name.to_string());
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SheetJson {
+    meta: SheetMeta,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SheetMeta {
+    image: String,
+    frame_tags: Vec<FrameTag>,
+}
+
+#[derive(Deserialize)]
+struct FrameTag {
+    name: String,
+}
+
+fn check_sheet(assets: &Path, path: &str, animations: &[(AnimState, &str)]) -> Result<(), String> {
+    let file = assets.join(path);
+    let tags = if path.ends_with(".aseprite") {
+        let bytes = fs::read(&file).map_err(|_| "missing".to_string())?;
+        let aseprite = parse_aseprite(&bytes).map_err(|error| error.to_string())?;
+        aseprite.tags.into_iter().map(|tag| tag.name).collect()
+    } else {
+        let contents = fs::read_to_string(&file).map_err(|_| "missing".to_string())?;
+        let sheet: SheetJson =
+            serde_json::from_str(&contents).map_err(|error| format!("invalid json ({})", error))?;
+
+        let image = file.parent().unwrap_or(assets).join(&sheet.meta.image);
+        if !image.is_file() {
+            return Err(format!("image {} is missing", sheet.meta.image));
+        }
+        sheet
+            .meta
+            .frame_tags
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect()
+    };
+    check_tags(animations, &tags)
+}
+
+// every tag the sheet's animation controller can play has to be exported
+fn check_tags(animations: &[(AnimState, &str)], tags: &[String]) -> Result<(), String> {
+    let missing: Vec<&str> = animations
+        .iter()
+        .map(|(_, tag)| *tag)
+        .filter(|tag| !tags.iter().any(|name| name == tag))
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("missing animation tags {}", missing.join(", ")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_tags_lists_the_missing_ones() {
+        let tags = vec!["idle".to_string(), "walk".to_string()];
+
+        assert_eq!(check_tags(&ENEMY_ANIMATIONS[..2], &tags), Ok(()));
+        assert_eq!(
+            check_tags(ENEMY_ANIMATIONS, &tags),
+            Err("missing animation tags attack, hurt, death".to_string())
+        );
+    }
+
+    #[test]
+    fn shipped_sheets_export_their_tags() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        for (path, animations) in SHEETS {
+            assert_eq!(check_sheet(&assets, path, animations), Ok(()), "{}", path);
+        }
+    }
+
+    #[test]
+    fn collection_paths_come_from_the_collections() {
+        let paths = collection_paths();
+
+        assert_eq!(
+            paths.get("npc_4.png").map(String::as_str),
+            Some("SpriteImageAssets")
+        );
+        assert_eq!(
+            paths
+                .get("monochrome_tilemap_transparent.png")
+                .map(String::as_str),
+            Some("TextureAssets")
+        );
+        assert_eq!(
+            paths.get("audio/music_game.wav").map(String::as_str),
+            Some("GameAudioAssets")
+        );
+    }
+}
@@ -1,3 +1,4 @@
+pub mod animation;
 pub mod audio;
 pub mod base;
 pub mod camera;
@@ -7,8 +8,10 @@ pub mod enemy;
 pub mod game;
 pub mod health;
 pub mod hurt;
+pub mod loading;
 pub mod particle;
 pub mod player;
+pub mod settings;
 pub mod ui;
 pub mod upgrade;
 pub mod window;
@@ -20,6 +23,10 @@ use bevy_rapier2d::render::RapierDebugRenderPlugin;
 use game::systems::GameStatePlugin;
 
 fn main() {
+    if std::env::args().any(|arg| arg == "--validate-assets") {
+        std::process::exit(loading::validate::run());
+    }
+
     App::new()
         .add_plugins(GameStatePlugin)
         // .add_plugins(RapierDebugRenderPlugin::default())
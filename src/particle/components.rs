@@ -17,7 +17,7 @@ impl Default for Particle {
             velocity: Vec2::ZERO,
             max_lifetime: 4.0,
             lifetime: 4.0,
-            gravity: -9.81,
+            gravity: -98.1,
         }
     }
 }
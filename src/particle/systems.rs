@@ -1,13 +1,16 @@
-
-
 use super::components::*;
+use crate::game::{components::GameState, tuning::Tuning};
 use bevy::prelude::*;
 
 pub struct ParticlePlugin;
 
 impl Plugin for ParticlePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_particle);
+        // particles move a fixed step per frame, so they have to be stopped explicitly
+        app.add_systems(
+            Update,
+            update_particle.run_if(not(in_state(GameState::Paused))),
+        );
     }
 }
 
@@ -15,6 +18,7 @@ fn update_particle(
     mut commands: Commands,
     mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
     time: Res<Time>,
+    tuning: Res<Tuning>,
 ) {
     for (entity, mut particle, mut transform, mut sprite) in &mut particle_query {
         particle.lifetime -= time.delta_seconds();
@@ -38,13 +42,13 @@ fn update_particle(
         }
 
         // apply gravity
-        new_translation.y += particle.gravity * 10.0 * time.delta_seconds();
+        new_translation.y += particle.gravity * time.delta_seconds();
 
         // bounce off the ground
-        let ground = 50.0;
+        let ground = tuning.particles.ground;
         if new_translation.y < particle.initial_position.y - ground {
             new_translation.y = particle.initial_position.y - ground;
-            particle.velocity.y = -particle.velocity.y * 0.7;
+            particle.velocity.y = -particle.velocity.y * tuning.particles.bounce;
         }
 
         // update the particle's position
@@ -1,21 +1,47 @@
 use bevy::prelude::*;
 
-use crate::base::components::*;
+use crate::{animation::components::AnimState, game::tuning::PlayerTuning};
 #[derive(Component)]
 pub struct Bullet {
+    pub weapon: Weapon,
     pub direction: Vec3,
     pub speed: f32,
     pub size: f32,
     pub damage: f32,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Weapon {
+    Blaster,
+}
+
+impl Weapon {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weapon::Blaster => "Blaster",
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct DamageDealtEvent {
+    pub weapon: Weapon,
+    pub amount: f32,
+}
+
 #[derive(Component)]
 pub struct TagPlayer;
 
+// Tags in npc_4.sprite.json, the player has no attack animation
+pub const PLAYER_ANIMATIONS: &[(AnimState, &str)] = &[
+    (AnimState::Idle, "idle_2"),
+    (AnimState::Move, "run"),
+    (AnimState::Hurt, "hurt"),
+    (AnimState::Death, "death"),
+];
+
 #[derive(Component)]
 pub struct Player {
-    pub idle: AnimationIndices,
-    pub walk: AnimationIndices,
     pub state: PlayerState,
     pub stats: PlayerStats,
 }
@@ -26,6 +52,10 @@ pub struct PlayerStats {
     pub bullet_speed: f32,
     pub bullet_damage: f32,
     pub move_speed: f32,
+    // radius of the sensor that starts pulling in xp
+    pub pickup_radius: f32,
+    // how hard collected xp is pulled towards the player
+    pub pickup_strength: f32,
 }
 
 pub struct PlayerState {
@@ -39,22 +69,35 @@ pub struct ShootTimer(pub Timer);
 #[derive(Component, Deref, DerefMut)]
 pub struct BulletDespawnTimer(pub Timer);
 
-impl Default for Player {
-    fn default() -> Self {
+impl Player {
+    pub fn new(tuning: &PlayerTuning) -> Self {
         Self {
-            idle: AnimationIndices { first: 6, last: 7 },
-            walk: AnimationIndices { first: 0, last: 3 },
             state: PlayerState {
                 moving: false,
                 facing: Vec3::new(1.0, 0.0, 0.0),
             },
             stats: PlayerStats {
-                size: 5.0,
-                shot_speed: 0.4,
-                move_speed: 300.0,
-                bullet_speed: 500.0,
-                bullet_damage: 1.0,
+                size: tuning.size,
+                shot_speed: tuning.shot_speed,
+                move_speed: tuning.move_speed,
+                bullet_speed: tuning.bullet_speed,
+                bullet_damage: tuning.bullet_damage,
+                pickup_radius: tuning.pickup_radius,
+                pickup_strength: tuning.pickup_strength,
             },
         }
     }
 }
+
+impl PlayerStats {
+    /// Moves every stat by as much as its base value changed, so upgrades taken
+    /// this run stay on top. Size only changes on the next run.
+    pub fn retune(&mut self, old: &PlayerTuning, new: &PlayerTuning) {
+        self.shot_speed += new.shot_speed - old.shot_speed;
+        self.move_speed += new.move_speed - old.move_speed;
+        self.bullet_speed += new.bullet_speed - old.bullet_speed;
+        self.bullet_damage += new.bullet_damage - old.bullet_damage;
+        self.pickup_radius += new.pickup_radius - old.pickup_radius;
+        self.pickup_strength += new.pickup_strength - old.pickup_strength;
+    }
+}
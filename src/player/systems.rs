@@ -1,16 +1,26 @@
-use bevy::{prelude::*, sprite::Anchor};
+use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_asepritesheet::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use super::components::*;
 use crate::{
-    audio::components::{PlaySoundEffectEvent, SoundEffectType},
-    camera::components::Target,
+    animation::{components::*, systems::AnimationSet},
+    audio::components::{SoundCue, SoundCueEvent},
+    camera::{
+        components::{Target, WorldCamera},
+        resources::PixelViewport,
+    },
     damagable::components::*,
     enemy::components::*,
-    game::components::GameState,
+    game::{
+        components::GameState,
+        run::*,
+        tuning::{PlayerTuning, Tuning},
+    },
     health::components::{Dead, Health},
     hurt::components::*,
+    loading::resources::SpriteSheets,
+    settings::resources::{AimMode, InputAction, Settings},
     xp::components::XPCollector,
 };
 
@@ -18,11 +28,11 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        // TODO: Move to assets plugin
-        app.add_plugins(AsepritesheetPlugin::new(&["sprite.json"]));
+        app.add_event::<DamageDealtEvent>();
 
         // Setup
-        app.add_systems(OnEnter(GameState::Playing), spawn_player);
+        app.add_systems(OnRunStart, spawn_player);
+        app.add_systems(Update, retune_player);
 
         // Playing state
         app.add_systems(
@@ -32,7 +42,7 @@ impl Plugin for PlayerPlugin {
                 kill_player,
                 hurt_player,
                 move_player,
-                animate_player,
+                animate_player.in_set(AnimationSet),
                 flip_player,
                 player_shoot,
                 update_bullets,
@@ -44,7 +54,12 @@ impl Plugin for PlayerPlugin {
         // Upgrade state
         app.add_systems(
             Update,
-            (animate_player, update_bullets, pause_move).run_if(in_state(GameState::Upgrade)),
+            (
+                animate_player.in_set(AnimationSet),
+                update_bullets,
+                pause_move,
+            )
+                .run_if(in_state(GameState::Upgrade)),
         );
     }
 }
@@ -56,39 +71,17 @@ fn pause_move(mut query: Query<(&mut Velocity, &mut Player)>) {
     }
 }
 
-fn spawn_player(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    player_query: Query<Option<&Player>>,
-) {
-    for player in &player_query {
-        if player.is_some() {
-            return;
-        }
-    }
-
-    let spritesheet_handle = load_spritesheet_then(
-        &mut commands,
-        &asset_server,
-        "npc_4.sprite.json",
-        Anchor::Center,
-        |sheet| {
-            let handle_death = sheet.get_anim_handle("death");
-
-            if let Ok(anim_death) = sheet.get_anim_mut(&handle_death) {
-                anim_death.end_action = AnimEndAction::Pause;
-            }
-        },
-    );
-
-    let player = Player::default();
+pub fn spawn_player(mut commands: Commands, sprite_sheets: Res<SpriteSheets>, tuning: Res<Tuning>) {
+    let player = Player::new(&tuning.player);
+    let size = player.stats.size;
+    let pickup_radius = player.stats.pickup_radius;
 
     commands
         .spawn((
             SpatialBundle::from(Transform::from_xyz(0.0, 0.0, 0.0)),
             Health {
-                max: 3.0,
-                current: 3.0,
+                max: tuning.player.health,
+                current: tuning.player.health,
             },
             ShootTimer(Timer::from_seconds(
                 player.stats.shot_speed,
@@ -99,18 +92,19 @@ fn spawn_player(
             ActiveEvents::COLLISION_EVENTS,
             LockedAxes::ROTATION_LOCKED,
             Damageable,
-            Target,
-            Player::default(),
+            Target::default(),
+            RunScoped,
+            player,
         ))
         .with_children(|parent| {
             parent.spawn((
-                Collider::ball(player.stats.size * 3.0),
+                Collider::ball(size * 3.0),
                 TransformBundle::from(Transform::from_xyz(0.0, -5.0, 0.0)),
                 TagPlayer,
             ));
 
             parent.spawn((
-                Collider::ball(100.0),
+                Collider::ball(pickup_radius),
                 Sensor,
                 ActiveEvents::COLLISION_EVENTS,
                 ActiveHooks::FILTER_INTERSECTION_PAIR,
@@ -120,11 +114,11 @@ fn spawn_player(
 
             parent.spawn((
                 AnimatedSpriteBundle {
-                    spritesheet: spritesheet_handle,
+                    spritesheet: sprite_sheets.player.clone(),
                     sprite_bundle: SpriteSheetBundle {
                         transform: Transform {
                             translation: Vec3::new(0.0, 0.0, 0.0),
-                            scale: Vec3::splat(player.stats.size),
+                            scale: Vec3::splat(size),
                             ..Default::default()
                         },
                         ..default()
@@ -132,23 +126,42 @@ fn spawn_player(
                     ..default()
                 },
                 AnimEventSender,
+                AnimationController::new(PLAYER_ANIMATIONS),
+                TintStack::default(),
                 TagPlayer,
             ));
         });
 }
 
+// applies live edits of tuning.ron to the running player
+fn retune_player(
+    tuning: Res<Tuning>,
+    mut previous: Local<Option<PlayerTuning>>,
+    mut query: Query<&mut Player>,
+) {
+    if !tuning.is_changed() {
+        return;
+    }
+
+    if let Some(previous) = previous.as_ref() {
+        if *previous != tuning.player {
+            for mut player in &mut query {
+                player.stats.retune(previous, &tuning.player);
+            }
+        }
+    }
+    *previous = Some(tuning.player.clone());
+}
+
+// flips the sprite itself, the transform scale is left to hit reactions
 fn flip_player(
     mut query: Query<(&Player, &Children), Without<Dead>>,
-    mut sprite_query: Query<&mut Transform, With<TextureAtlasSprite>>,
+    mut sprite_query: Query<&mut TextureAtlasSprite>,
 ) {
     for (player, children) in &mut query {
         for child in children {
-            if let Ok(mut transform) = sprite_query.get_mut(*child) {
-                if player.state.facing.x < 0.0 {
-                    transform.scale.x = -player.stats.size;
-                } else {
-                    transform.scale.x = player.stats.size;
-                }
+            if let Ok(mut sprite) = sprite_query.get_mut(*child) {
+                sprite.flip_x = player.state.facing.x < 0.0;
             }
         }
     }
@@ -156,13 +169,15 @@ fn flip_player(
 
 fn move_player(
     keyboard_input: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
     mut query: Query<(&mut Player, &mut Velocity), Without<Dead>>,
 ) {
+    let controls = &settings.controls;
     for (mut player, mut vel) in &mut query {
-        let up = keyboard_input.any_pressed([KeyCode::W]);
-        let down = keyboard_input.any_pressed([KeyCode::S]);
-        let left = keyboard_input.any_pressed([KeyCode::A]);
-        let right = keyboard_input.any_pressed([KeyCode::D]);
+        let up = controls.pressed(&keyboard_input, InputAction::MoveUp);
+        let down = controls.pressed(&keyboard_input, InputAction::MoveDown);
+        let left = controls.pressed(&keyboard_input, InputAction::MoveLeft);
+        let right = controls.pressed(&keyboard_input, InputAction::MoveRight);
 
         let y_axis = -(left as i8) + right as i8;
         let x_axis = -(down as i8) + up as i8;
@@ -191,72 +206,122 @@ fn move_player(
 }
 
 fn animate_player(
-    mut q_animator: Query<&mut SpriteAnimator, With<TagPlayer>>,
+    mut q_controller: Query<&mut AnimationController, With<TagPlayer>>,
     q_player: Query<(&Health, &Player, &Children)>,
 ) {
     for (health, player, children) in &q_player {
         for child in children {
-            if let Ok(mut sprite_animator) = q_animator.get_mut(*child) {
+            if let Ok(mut controller) = q_controller.get_mut(*child) {
                 if health.current <= 0.0 {
-                    sprite_animator.time_scale = 1.0;
-                    sprite_animator.set_anim_index(4);
+                    controller.set_state(AnimState::Death);
                 } else if player.state.moving {
-                    sprite_animator.time_scale = 1.0;
-                    sprite_animator.set_anim_index(0);
+                    controller.set_state(AnimState::Move);
                 } else {
-                    sprite_animator.time_scale = 1.0;
-                    sprite_animator.set_anim_index(2);
+                    controller.set_state(AnimState::Idle);
                 }
             }
         }
     }
 }
 
+// Direction to shoot in this frame for the chosen aim mode, if any
+fn aim_direction(
+    settings: &Settings,
+    keyboard_input: &Input<KeyCode>,
+    mouse_input: &Input<MouseButton>,
+    cursor: Option<Vec2>,
+    closest_enemy: Option<Vec2>,
+    position: Vec2,
+) -> Option<Vec2> {
+    match settings.gameplay.aim_mode {
+        AimMode::Keys => {
+            let controls = &settings.controls;
+            // the last pressed direction in this order wins, like before bindings
+            [
+                (InputAction::ShootLeft, Vec2::NEG_X),
+                (InputAction::ShootRight, Vec2::X),
+                (InputAction::ShootUp, Vec2::Y),
+                (InputAction::ShootDown, Vec2::NEG_Y),
+            ]
+            .into_iter()
+            .filter(|(action, _)| controls.pressed(keyboard_input, *action))
+            .map(|(_, direction)| direction)
+            .last()
+        }
+        AimMode::Mouse => {
+            if !mouse_input.pressed(MouseButton::Left) {
+                return None;
+            }
+            (cursor? - position).try_normalize()
+        }
+        AimMode::Auto => (closest_enemy? - position).try_normalize(),
+    }
+}
+
 fn player_shoot(
     mut commands: Commands,
-    mut query: Query<(&Player, &Transform, &mut ShootTimer)>,
+    mut query: Query<(&Player, &Transform, &mut ShootTimer, &mut Target)>,
     keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<WorldCamera>>,
+    viewport: Res<PixelViewport>,
+    enemy_query: Query<&Transform, (With<Enemy>, Without<Dead>)>,
+    settings: Res<Settings>,
     time: Res<Time>,
-    _meshes: ResMut<Assets<Mesh>>,
-    _materials: ResMut<Assets<ColorMaterial>>,
-    mut event_sound: EventWriter<PlaySoundEffectEvent>,
+    mut event_sound: EventWriter<SoundCueEvent>,
+    tuning: Res<Tuning>,
 ) {
-    let mut shooting: bool = false;
-    let mut direction: Vec3 = Vec3::ZERO;
-    for (player, transform, mut shoot_timer) in &mut query {
+    let cursor = window_query
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .zip(camera_query.get_single().ok())
+        .and_then(|(cursor, (camera, transform))| {
+            // the world camera draws into the canvas, not the window
+            let size = camera.logical_viewport_size()?;
+            let cursor = viewport.to_virtual(cursor, size)?;
+            camera.viewport_to_world_2d(transform, cursor)
+        });
+
+    for (player, transform, mut shoot_timer, mut target) in &mut query {
         let offset = 10.0;
-        let mut translation_with_offset = Vec3::ZERO;
-        if keyboard_input.pressed(KeyCode::Left) {
-            shooting = true;
-            direction = Vec3::new(-1.0, 0.0, 0.0);
-            translation_with_offset = transform.translation + Vec3::new(-offset, 0.0, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Right) {
-            shooting = true;
-            direction = Vec3::new(1.0, 0.0, 0.0);
-            translation_with_offset = transform.translation + Vec3::new(offset, 0.0, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Up) {
-            shooting = true;
-            direction = Vec3::new(0.0, 1.0, 0.0);
-            translation_with_offset = transform.translation + Vec3::new(0.0, offset, 0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Down) {
-            shooting = true;
-            direction = Vec3::new(0.0, -1.0, 0.0);
-            translation_with_offset = transform.translation + Vec3::new(0.0, -offset, 0.0);
-        }
+        let position = transform.translation.truncate();
+
+        let closest_enemy = enemy_query
+            .iter()
+            .map(|enemy| enemy.translation.truncate())
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        let aim = aim_direction(
+            &settings,
+            &keyboard_input,
+            &mouse_input,
+            cursor,
+            closest_enemy,
+            position,
+        );
+        let shooting = aim.is_some();
+        target.aim = aim.unwrap_or(Vec2::ZERO);
+        let direction = aim.unwrap_or(Vec2::ZERO).extend(0.0);
+        let translation_with_offset = transform.translation + direction * offset;
+
         if shooting {
             let duration: f32 = shoot_timer.elapsed_secs();
 
             if duration >= player.stats.shot_speed {
                 // send player sound event
-                event_sound.send(PlaySoundEffectEvent {
-                    sound: SoundEffectType::PlayerShoot,
+                event_sound.send(SoundCueEvent {
+                    cue: SoundCue::PlayerShot,
+                    position: None,
                 });
                 shoot_timer.reset();
                 // spawn bullet
                 let bullet = Bullet {
+                    weapon: Weapon::Blaster,
                     direction,
                     speed: player.stats.bullet_speed,
                     size: player.stats.bullet_damage * 5.0,
@@ -281,7 +346,11 @@ fn player_shoot(
                     RigidBody::Dynamic,
                     Velocity::zero(),
                     Collider::ball(bullet.size),
-                    BulletDespawnTimer(Timer::from_seconds(3.0, TimerMode::Once)),
+                    BulletDespawnTimer(Timer::from_seconds(
+                        tuning.player.bullet_lifetime,
+                        TimerMode::Once,
+                    )),
+                    RunScoped,
                     ActiveEvents::COLLISION_EVENTS,
                     Damage(bullet.damage),
                     bullet,
@@ -319,7 +388,7 @@ fn hurt_player(
     parent_query: Query<&Parent>,
     damage_query: Query<(Entity, &Damage), With<TagEnemy>>,
     rapier_context: Res<RapierContext>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    mut sound_event: EventWriter<SoundCueEvent>,
 ) {
     for _ in collision_events.read() {
         for player_collider_entity in &mut player_collider_query {
@@ -328,8 +397,9 @@ fn hurt_player(
                     rapier_context.contact_pair(damage_entity, player_collider_entity)
                 {
                     for parent in parent_query.iter_ancestors(player_collider_entity) {
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::PlayerHurt,
+                        sound_event.send(SoundCueEvent {
+                            cue: SoundCue::PlayerHit,
+                            position: None,
                         });
                         commands.entity(parent).insert(Hurting(damage_source.0));
                     }
@@ -342,9 +412,14 @@ fn hurt_player(
 fn kill_player(
     mut commands: Commands,
     mut player_query: Query<(Entity, &Health, &mut Velocity), (With<Player>, Without<Dead>)>,
+    mut sound_event: EventWriter<SoundCueEvent>,
 ) {
     for (entity, health, mut velocity) in &mut player_query {
         if health.current <= 0.0 {
+            sound_event.send(SoundCueEvent {
+                cue: SoundCue::PlayerDied,
+                position: None,
+            });
             // commands.entity(entity).despawn()
             commands.entity(entity).insert(Dead);
             commands.entity(entity).remove::<Collider>();
@@ -368,7 +443,7 @@ fn cleanup_dead(
             let player_entity = player_query.get(parent);
             if let Ok(entity) = player_entity {
                 println!("Player death animation finished");
-                next_state.set(GameState::Menu);
+                next_state.set(GameState::GameOver);
                 commands.entity(entity).despawn_recursive();
             }
         }
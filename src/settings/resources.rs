@@ -0,0 +1,360 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::*;
+use ron::{error::SpannedError, ser::PrettyConfig};
+use serde::{Deserialize, Serialize};
+
+/// Bumped whenever a change needs more than new fields with defaults,
+/// together with a step in `migrate`
+pub const SETTINGS_VERSION: u32 = 1;
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+/// Player options, stored in the platform config dir and written on every change
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub audio: AudioSettings,
+    pub video: VideoSettings,
+    pub gameplay: GameplaySettings,
+    pub controls: ControlSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            audio: AudioSettings::default(),
+            video: VideoSettings::default(),
+            gameplay: GameplaySettings::default(),
+            controls: ControlSettings::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    // every volume goes from 0.0 to 1.0
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+            ui: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn music_volume(&self) -> f64 {
+        (self.master * self.music) as f64
+    }
+
+    pub fn sfx_volume(&self) -> f64 {
+        (self.master * self.sfx) as f64
+    }
+
+    pub fn ui_volume(&self) -> f64 {
+        (self.master * self.ui) as f64
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowModeSetting {
+    Windowed,
+    Fullscreen,
+    Borderless,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub window_mode: WindowModeSetting,
+    // width and height of the window in windowed mode
+    pub resolution: (u32, u32),
+    pub vsync: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            window_mode: WindowModeSetting::Windowed,
+            resolution: (1280, 720),
+            vsync: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AimMode {
+    // shoot in the direction of the arrow keys
+    Keys,
+    // shoot towards the mouse cursor
+    Mouse,
+    // shoot at the closest enemy
+    Auto,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct GameplaySettings {
+    // strength of screen shake, hit-stop and flashes, 0.0 turns them off
+    #[serde(alias = "screen_shake")]
+    pub screen_effects: f32,
+    pub damage_numbers: bool,
+    pub aim_mode: AimMode,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            screen_effects: 1.0,
+            damage_numbers: true,
+            aim_mode: AimMode::Keys,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    ShootUp,
+    ShootDown,
+    ShootLeft,
+    ShootRight,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 8] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::ShootUp,
+        InputAction::ShootDown,
+        InputAction::ShootLeft,
+        InputAction::ShootRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Move Up",
+            InputAction::MoveDown => "Move Down",
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::ShootUp => "Shoot Up",
+            InputAction::ShootDown => "Shoot Down",
+            InputAction::ShootLeft => "Shoot Left",
+            InputAction::ShootRight => "Shoot Right",
+        }
+    }
+}
+
+/// Keyboard bindings for every `InputAction`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct ControlSettings {
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub shoot_up: KeyCode,
+    pub shoot_down: KeyCode,
+    pub shoot_left: KeyCode,
+    pub shoot_right: KeyCode,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            move_up: KeyCode::W,
+            move_down: KeyCode::S,
+            move_left: KeyCode::A,
+            move_right: KeyCode::D,
+            shoot_up: KeyCode::Up,
+            shoot_down: KeyCode::Down,
+            shoot_left: KeyCode::Left,
+            shoot_right: KeyCode::Right,
+        }
+    }
+}
+
+impl ControlSettings {
+    pub fn key(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::MoveUp => self.move_up,
+            InputAction::MoveDown => self.move_down,
+            InputAction::MoveLeft => self.move_left,
+            InputAction::MoveRight => self.move_right,
+            InputAction::ShootUp => self.shoot_up,
+            InputAction::ShootDown => self.shoot_down,
+            InputAction::ShootLeft => self.shoot_left,
+            InputAction::ShootRight => self.shoot_right,
+        }
+    }
+
+    fn key_mut(&mut self, action: InputAction) -> &mut KeyCode {
+        match action {
+            InputAction::MoveUp => &mut self.move_up,
+            InputAction::MoveDown => &mut self.move_down,
+            InputAction::MoveLeft => &mut self.move_left,
+            InputAction::MoveRight => &mut self.move_right,
+            InputAction::ShootUp => &mut self.shoot_up,
+            InputAction::ShootDown => &mut self.shoot_down,
+            InputAction::ShootLeft => &mut self.shoot_left,
+            InputAction::ShootRight => &mut self.shoot_right,
+        }
+    }
+
+    /// Binds `key` to `action`. An action that already used the key gets the old
+    /// key of `action` instead, so no two actions ever share a key.
+    pub fn bind(&mut self, action: InputAction, key: KeyCode) {
+        let old = self.key(action);
+        if let Some(other) = InputAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.key(*other) == key)
+        {
+            *self.key_mut(other) = old;
+        }
+        *self.key_mut(action) = key;
+    }
+
+    pub fn pressed(&self, input: &Input<KeyCode>, action: InputAction) -> bool {
+        input.pressed(self.key(action))
+    }
+}
+
+impl Settings {
+    /// Reads the settings file, falling back to defaults when it is missing or broken
+    pub fn load() -> Self {
+        let Some(path) = settings_path() else {
+            return Self::default();
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Self::parse(&contents).unwrap_or_else(|error| {
+                warn!("Could not read {}: {}", path.display(), error);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = settings_path() else {
+            return;
+        };
+
+        let result = ron::ser::to_string_pretty(self, PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|contents| write_file(&path, &contents).map_err(|error| error.to_string()));
+        if let Err(error) = result {
+            warn!("Could not write {}: {}", path.display(), error);
+        }
+    }
+
+    /// Parses a settings file of any version, migrating it to the current one
+    pub fn parse(contents: &str) -> Result<Self, SpannedError> {
+        let FileVersion { version } = ron::from_str(contents)?;
+
+        let mut settings: Settings = ron::from_str(contents)?;
+        migrate(&mut settings, version);
+        Ok(settings)
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+#[derive(Deserialize)]
+struct FileVersion {
+    // files written before versioning count as version 1
+    #[serde(default = "first_version")]
+    version: u32,
+}
+
+fn first_version() -> u32 {
+    1
+}
+
+// Upgrades settings read from an older file one version at a time. Fields
+// added with a default need no step, serde already filled them in.
+fn migrate(settings: &mut Settings, from: u32) {
+    // no version changed the meaning of a field yet, steps go here as `if from < 2 { .. }`
+    let _ = from;
+    settings.version = SETTINGS_VERSION;
+}
+
+/// `$XDG_CONFIG_HOME/rustpg/settings.ron`, or the platform config dir
+pub fn settings_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("rustpg").join(SETTINGS_FILE))
+}
+
+fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binding_a_used_key_swaps_the_two_actions() {
+        let mut controls = ControlSettings::default();
+        controls.bind(InputAction::MoveUp, KeyCode::Up);
+
+        assert_eq!(controls.key(InputAction::MoveUp), KeyCode::Up);
+        assert_eq!(controls.key(InputAction::ShootUp), KeyCode::W);
+    }
+
+    #[test]
+    fn round_trips_through_ron() {
+        let mut settings = Settings::default();
+        settings.audio.music = 0.25;
+        settings.video.window_mode = WindowModeSetting::Borderless;
+        settings.gameplay.aim_mode = AimMode::Mouse;
+        settings.controls.bind(InputAction::ShootLeft, KeyCode::J);
+
+        let contents = ron::ser::to_string_pretty(&settings, PrettyConfig::default()).unwrap();
+        assert_eq!(Settings::parse(&contents).unwrap(), settings);
+    }
+
+    #[test]
+    fn old_files_get_defaults_for_new_fields() {
+        let settings = Settings::parse("(audio: (master: 0.5))").unwrap();
+
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.audio.master, 0.5);
+        assert_eq!(settings.audio.music, 1.0);
+        assert_eq!(settings.video, VideoSettings::default());
+    }
+}
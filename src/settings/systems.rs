@@ -0,0 +1,57 @@
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+
+use super::resources::*;
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // read right away, the window is created from these settings
+        app.insert_resource(Settings::load())
+            .add_systems(Update, (apply_video_settings, save_settings));
+    }
+}
+
+pub fn configure_window(window: &mut Window, video: &VideoSettings) {
+    window.mode = match video.window_mode {
+        WindowModeSetting::Windowed => WindowMode::Windowed,
+        WindowModeSetting::Fullscreen => WindowMode::Fullscreen,
+        WindowModeSetting::Borderless => WindowMode::BorderlessFullscreen,
+    };
+    window
+        .resolution
+        .set(video.resolution.0 as f32, video.resolution.1 as f32);
+    window.present_mode = if video.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::Immediate
+    };
+}
+
+// only touches the window when a video option changed, so a window the player
+// resized by hand keeps its size while they change the volume
+fn apply_video_settings(
+    settings: Res<Settings>,
+    mut previous: Local<Option<VideoSettings>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if previous.as_ref() == Some(&settings.video) {
+        return;
+    }
+
+    if previous.is_some() {
+        for mut window in &mut window_query {
+            configure_window(&mut window, &settings.video);
+        }
+    }
+    *previous = Some(settings.video.clone());
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save();
+    }
+}
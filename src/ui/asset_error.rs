@@ -0,0 +1,79 @@
+use bevy::{app::AppExit, prelude::*};
+
+use super::{
+    components::{AssetErrorQuitButton, TagAssetErrorScreen},
+    widgets::{button_style, spawn_button, ButtonClickEvent, ButtonSounds, UiTheme},
+};
+use crate::loading::resources::FailedAssets;
+
+pub fn setup_screen(mut commands: Commands, failed: Res<FailedAssets>, theme: Res<UiTheme>) {
+    let mut lines = vec!["The game could not load these files:".to_string()];
+    lines.extend(failed.critical.iter().map(|path| format!("  {}", path)));
+    if failed.critical.is_empty() {
+        lines.push("  (see the log for details)".to_string());
+    }
+    if !failed.optional.is_empty() {
+        lines.push("Also missing, but not required:".to_string());
+        lines.extend(failed.optional.iter().map(|path| format!("  {}", path)));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            TagAssetErrorScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Missing Assets",
+                TextStyle {
+                    font_size: 50.0,
+                    color: Color::RED,
+                    ..default()
+                },
+            ));
+
+            parent.spawn(TextBundle::from_section(
+                lines.join("\n"),
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            // no sounds here, the audio might be what failed to load
+            spawn_button(
+                parent,
+                &theme,
+                "Quit",
+                button_style(200.0, 60.0),
+                (AssetErrorQuitButton, ButtonSounds::SILENT),
+            );
+        });
+}
+
+pub fn update_screen(
+    mut exit: EventWriter<AppExit>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    quit_query: Query<(), With<AssetErrorQuitButton>>,
+) {
+    for event in click_events.read() {
+        if quit_query.contains(event.entity) {
+            exit.send(AppExit);
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::upgrade::components::Upgrade;
+
 #[derive(Component)]
 pub struct TagGameUI;
 
@@ -9,43 +11,46 @@ pub struct TagMainMenu;
 #[derive(Component)]
 pub struct TagUpgradeMenu;
 
-pub struct ButtonStyle {
-    pub background: ButtonBackground,
-    pub foreground: ButtonForeground,
-}
+#[derive(Component)]
+pub struct TagPauseMenu;
 
-pub struct ButtonBackground {
-    pub default: BackgroundColor,
-    pub hover: BackgroundColor,
-    pub active: BackgroundColor,
-}
+#[derive(Component)]
+pub struct TagGameOverMenu;
 
-pub struct ButtonForeground {
-    pub default: Color,
-    pub hover: Color,
-    pub active: Color,
+#[derive(Component)]
+pub struct TagLoadingScreen;
+
+#[derive(Component)]
+pub struct TagSplash;
+
+#[derive(Component)]
+pub struct TagAssetErrorScreen;
+
+#[derive(Component)]
+pub struct AssetErrorQuitButton;
+
+#[derive(Component)]
+pub struct TagSettingsMenu;
+
+/// An upgrade card, picking it takes the upgrade
+#[derive(Component)]
+pub struct UpgradeCard(pub Upgrade);
+
+#[derive(Component)]
+pub enum MainMenuButtonAction {
+    Play,
+    Settings,
 }
 
 #[derive(Component)]
-pub enum UpgradeButtonAction {
-    Upgrade1,
-    Upgrade2,
-    Upgrade3,
+pub enum PauseButtonAction {
+    Resume,
+    Settings,
+    QuitToMenu,
 }
 
-impl Default for ButtonStyle {
-    fn default() -> Self {
-        ButtonStyle {
-            background: ButtonBackground {
-                default: Color::rgb(0.0, 0.0, 0.0).into(),
-                hover: Color::rgb(1.0, 1.0, 1.0).into(),
-                active: Color::rgb(0.5, 0.5, 0.5).into(),
-            },
-            foreground: ButtonForeground {
-                default: Color::WHITE,
-                hover: Color::BLACK,
-                active: Color::BLACK,
-            },
-        }
-    }
+#[derive(Component)]
+pub enum GameOverButtonAction {
+    Retry,
+    MainMenu,
 }
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    components::{GameRules, GameState},
+    stats::RunStats,
+};
+
+use super::{
+    components::{GameOverButtonAction, TagGameOverMenu},
+    widgets::{button_style, spawn_button, ButtonClickEvent, UiTheme},
+};
+
+pub fn setup_menu(
+    mut commands: Commands,
+    game: Res<GameRules>,
+    stats: Res<RunStats>,
+    theme: Res<UiTheme>,
+) {
+    let mut lines = vec![
+        format!(
+            "Time survived: {}:{:02}",
+            stats.time_survived as u32 / 60,
+            stats.time_survived as u32 % 60
+        ),
+        format!("Level reached: {}", game.level),
+        format!("XP collected: {}", stats.xp_collected),
+        format!("Enemies killed: {}", stats.total_kills()),
+    ];
+
+    let mut kills: Vec<_> = stats.kills.iter().collect();
+    kills.sort();
+    for (kind, count) in kills {
+        lines.push(format!("  {}: {}", kind.name(), count));
+    }
+
+    let mut damage: Vec<_> = stats.damage_dealt.iter().collect();
+    damage.sort_by_key(|(weapon, _)| **weapon);
+    for (weapon, amount) in damage {
+        lines.push(format!("{} damage: {:.0}", weapon.name(), amount));
+    }
+
+    if stats.upgrades.is_empty() {
+        lines.push("Upgrades: none".to_string());
+    } else {
+        lines.push(format!("Upgrades: {}", stats.upgrades.join(", ")));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::BLACK.with_a(0.8).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            TagGameOverMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Game Over",
+                TextStyle {
+                    font_size: 50.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent.spawn(TextBundle::from_section(
+                lines.join("\n"),
+                TextStyle {
+                    font_size: 24.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for (label, action) in [
+                        ("Retry", GameOverButtonAction::Retry),
+                        ("Main Menu", GameOverButtonAction::MainMenu),
+                    ] {
+                        spawn_button(parent, &theme, label, button_style(200.0, 60.0), action);
+                    }
+                });
+        });
+}
+
+pub fn update_menu(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    action_query: Query<&GameOverButtonAction>,
+) {
+    for event in click_events.read() {
+        match action_query.get(event.entity) {
+            Ok(GameOverButtonAction::Retry) => next_state.set(GameState::Playing),
+            Ok(GameOverButtonAction::MainMenu) => next_state.set(GameState::Menu),
+            Err(_) => {}
+        }
+    }
+}
+
+pub fn cleanup_menu(mut commands: Commands, menu_query: Query<Entity, With<TagGameOverMenu>>) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
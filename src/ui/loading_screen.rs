@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use iyes_progress::prelude::*;
+
+use super::components::TagLoadingScreen;
+
+#[derive(Component)]
+pub struct UILoadingBar;
+
+pub fn setup_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            TagLoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Loading",
+                TextStyle {
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(400.0),
+                        height: Val::Px(20.0),
+                        border: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+                    border_color: Color::WHITE.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                        UILoadingBar,
+                    ));
+                });
+        });
+}
+
+pub fn update_loading_screen(
+    progress: Option<Res<ProgressCounter>>,
+    mut bar_query: Query<&mut Style, With<UILoadingBar>>,
+) {
+    let Some(progress) = progress else {
+        return;
+    };
+
+    let progress: f32 = progress.progress().into();
+    for mut style in &mut bar_query {
+        style.width = Val::Percent(progress * 100.0);
+    }
+}
+
+pub fn cleanup_loading_screen(
+    mut commands: Commands,
+    screen_query: Query<Entity, With<TagLoadingScreen>>,
+) {
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
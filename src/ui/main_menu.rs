@@ -1,13 +1,14 @@
 use bevy::prelude::*;
 
-use crate::{
-    audio::components::{PlaySoundEffectEvent, SoundEffectType},
-    game::components::GameState,
-};
+use crate::game::components::GameState;
 
-use super::components::{ButtonStyle, TagMainMenu};
+use super::{
+    components::{MainMenuButtonAction, TagMainMenu},
+    settings_menu::SettingsMenu,
+    widgets::{button_style, spawn_button, ButtonClickEvent, UiTheme},
+};
 
-pub fn setup_menu(mut commands: Commands) {
+pub fn setup_menu(mut commands: Commands, theme: Res<UiTheme>) {
     commands
         .spawn((
             NodeBundle {
@@ -18,6 +19,7 @@ pub fn setup_menu(mut commands: Commands) {
                     flex_direction: FlexDirection::Column,
                     align_items: AlignItems::Center,
                     justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
                     ..default()
                 },
                 ..default()
@@ -25,69 +27,26 @@ pub fn setup_menu(mut commands: Commands) {
             TagMainMenu,
         ))
         .with_children(|parent| {
-            // Play button
-            parent
-                .spawn(ButtonBundle {
-                    style: Style {
-                        width: Val::Px(200.0),
-                        height: Val::Px(60.0),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        border: UiRect::all(Val::Px(2.0)),
-                        ..default()
-                    },
-                    background_color: ButtonStyle::default().background.default,
-                    border_color: Color::WHITE.into(),
-                    ..default()
-                })
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Play",
-                        TextStyle {
-                            font_size: 30.0,
-                            color: ButtonStyle::default().foreground.default,
-                            ..default()
-                        },
-                    ));
-                });
+            for (label, action) in [
+                ("Play", MainMenuButtonAction::Play),
+                ("Settings", MainMenuButtonAction::Settings),
+            ] {
+                spawn_button(parent, &theme, label, button_style(200.0, 60.0), action);
+            }
         });
 }
 
 pub fn update_menu(
     mut next_state: ResMut<NextState<GameState>>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut text_query: Query<&mut Text>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    mut settings_menu: ResMut<SettingsMenu>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    action_query: Query<&MainMenuButtonAction>,
 ) {
-    for (interaction, mut color, children) in &mut interaction_query {
-        for child in children {
-            let text = text_query.get_mut(*child);
-            if let Ok(mut text) = text {
-                match *interaction {
-                    Interaction::Pressed => {
-                        *color = ButtonStyle::default().background.active;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.active;
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::UIEnter,
-                        });
-                        next_state.set(GameState::Playing);
-                    }
-                    Interaction::Hovered => {
-                        *color = ButtonStyle::default().background.hover;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.hover;
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::UIHover,
-                        });
-                    }
-                    Interaction::None => {
-                        *color = ButtonStyle::default().background.default;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.default;
-                    }
-                }
-            }
+    for event in click_events.read() {
+        match action_query.get(event.entity) {
+            Ok(MainMenuButtonAction::Play) => next_state.set(GameState::Playing),
+            Ok(MainMenuButtonAction::Settings) => *settings_menu = SettingsMenu::open(),
+            Err(_) => {}
         }
     }
 }
@@ -1,18 +1,59 @@
+pub mod asset_error;
 pub mod components;
+pub mod game_over;
+pub mod loading_screen;
 pub mod main_menu;
+pub mod pause_menu;
+pub mod settings_menu;
+pub mod splash;
 pub mod systems;
 pub mod upgrade_menu;
+pub mod widgets;
 
-use crate::game::components::GameState;
+use crate::game::{components::GameState, run::OnRunStart};
 use bevy::prelude::*;
+use widgets::{WidgetPlugin, WidgetSet};
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
+        // Buttons and focus navigation shared by every menu
+        app.add_plugins(WidgetPlugin);
+
+        // Loading Screen
+        app.add_systems(
+            OnEnter(GameState::Loading),
+            loading_screen::setup_loading_screen,
+        )
+        .add_systems(
+            Update,
+            loading_screen::update_loading_screen.run_if(in_state(GameState::Loading)),
+        )
+        .add_systems(
+            OnExit(GameState::Loading),
+            loading_screen::cleanup_loading_screen,
+        );
+
+        // Asset Error
+        app.add_systems(OnEnter(GameState::AssetError), asset_error::setup_screen)
+            .add_systems(
+                Update,
+                asset_error::update_screen
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::AssetError)),
+            );
+
+        // Splash
+        app.add_systems(OnEnter(GameState::Splash), splash::setup_splash)
+            .add_systems(
+                Update,
+                splash::update_splash.run_if(in_state(GameState::Splash)),
+            )
+            .add_systems(OnExit(GameState::Splash), splash::cleanup_splash);
+
         // Game UI
-        app.add_systems(OnEnter(GameState::Playing), systems::setup_game_ui)
-            .add_systems(OnExit(GameState::Playing), systems::despawn_game_ui)
+        app.add_systems(OnRunStart, systems::setup_game_ui)
             .add_systems(
                 Update,
                 systems::update_ui.run_if(in_state(GameState::Playing)),
@@ -22,7 +63,9 @@ impl Plugin for UIPlugin {
         app.add_systems(OnEnter(GameState::Menu), main_menu::setup_menu)
             .add_systems(
                 Update,
-                main_menu::update_menu.run_if(in_state(GameState::Menu)),
+                main_menu::update_menu
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::Menu)),
             )
             .add_systems(OnExit(GameState::Menu), main_menu::cleanup_menu);
 
@@ -30,8 +73,56 @@ impl Plugin for UIPlugin {
         app.add_systems(OnEnter(GameState::Upgrade), upgrade_menu::setup_menu)
             .add_systems(
                 Update,
-                upgrade_menu::update_menu.run_if(in_state(GameState::Upgrade)),
+                (upgrade_menu::enable_cards, upgrade_menu::update_menu)
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::Upgrade)),
             )
             .add_systems(OnExit(GameState::Upgrade), upgrade_menu::cleanup_menu);
+
+        // Game Over
+        app.add_systems(OnEnter(GameState::GameOver), game_over::setup_menu)
+            .add_systems(
+                Update,
+                game_over::update_menu
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::GameOver)),
+            )
+            .add_systems(OnExit(GameState::GameOver), game_over::cleanup_menu);
+
+        // Pause Menu
+        app.add_systems(OnEnter(GameState::Paused), pause_menu::setup_menu)
+            .add_systems(
+                Update,
+                pause_menu::update_menu
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::Paused)),
+            )
+            .add_systems(OnExit(GameState::Paused), pause_menu::cleanup_menu);
+
+        // Settings, opened on top of the main or pause menu
+        app.init_resource::<settings_menu::SettingsMenu>()
+            .add_systems(
+                Update,
+                (
+                    settings_menu::sync_settings_menu,
+                    (
+                        settings_menu::close_on_back,
+                        settings_menu::click_settings_menu,
+                        settings_menu::rebind_key,
+                    )
+                        .chain()
+                        .run_if(settings_menu::settings_menu_open),
+                    settings_menu::update_settings_menu,
+                )
+                    .chain()
+                    .in_set(settings_menu::SettingsMenuSet)
+                    .after(WidgetSet)
+                    .run_if(in_state(GameState::Menu).or_else(in_state(GameState::Paused))),
+            )
+            .add_systems(OnExit(GameState::Menu), settings_menu::close_settings_menu)
+            .add_systems(
+                OnExit(GameState::Paused),
+                settings_menu::close_settings_menu,
+            );
     }
 }
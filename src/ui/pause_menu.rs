@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+
+use crate::game::components::GameState;
+
+use super::{
+    components::{PauseButtonAction, TagPauseMenu},
+    settings_menu::SettingsMenu,
+    widgets::{button_style, spawn_button, ButtonClickEvent, UiTheme},
+};
+
+pub fn setup_menu(mut commands: Commands, theme: Res<UiTheme>) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                // dim the frozen game behind the menu
+                background_color: Color::BLACK.with_a(0.6).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            TagPauseMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Paused",
+                TextStyle {
+                    font_size: 50.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            for (label, action) in [
+                ("Resume", PauseButtonAction::Resume),
+                ("Settings", PauseButtonAction::Settings),
+                ("Quit to Menu", PauseButtonAction::QuitToMenu),
+            ] {
+                spawn_button(parent, &theme, label, button_style(250.0, 60.0), action);
+            }
+        });
+}
+
+pub fn update_menu(
+    mut next_state: ResMut<NextState<GameState>>,
+    mut settings_menu: ResMut<SettingsMenu>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    action_query: Query<&PauseButtonAction>,
+) {
+    for event in click_events.read() {
+        match action_query.get(event.entity) {
+            Ok(PauseButtonAction::Resume) => next_state.set(GameState::Playing),
+            Ok(PauseButtonAction::Settings) => *settings_menu = SettingsMenu::open(),
+            Ok(PauseButtonAction::QuitToMenu) => next_state.set(GameState::Menu),
+            Err(_) => {}
+        }
+    }
+}
+
+pub fn cleanup_menu(mut commands: Commands, menu_query: Query<Entity, With<TagPauseMenu>>) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
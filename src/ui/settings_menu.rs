@@ -0,0 +1,661 @@
+use bevy::{
+    prelude::*,
+    ui::{FocusPolicy, RelativeCursorPosition},
+};
+
+use crate::{
+    audio::components::{PlaySoundEffectEvent, SoundEffectType},
+    settings::resources::*,
+};
+
+use super::{components::TagSettingsMenu, widgets::*};
+
+/// Whether the settings screen is open on top of the main or pause menu
+#[derive(Resource, Default)]
+pub struct SettingsMenu {
+    pub open: bool,
+    // the dropdown that is expanded
+    dropdown: Option<SettingsOption>,
+    // waiting for a key to bind to this action
+    rebinding: Option<InputAction>,
+}
+
+impl SettingsMenu {
+    pub fn open() -> Self {
+        Self {
+            open: true,
+            ..default()
+        }
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SettingsMenuSet;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SettingsOption {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    UiVolume,
+    WindowMode,
+    Resolution,
+    Vsync,
+    ScreenEffects,
+    DamageNumbers,
+    AimMode,
+    Binding(InputAction),
+    Back,
+}
+
+enum OptionKind {
+    Slider,
+    Toggle,
+    Dropdown,
+    Binding,
+    Button,
+}
+
+const GENERAL_OPTIONS: [SettingsOption; 10] = [
+    SettingsOption::MasterVolume,
+    SettingsOption::MusicVolume,
+    SettingsOption::SfxVolume,
+    SettingsOption::UiVolume,
+    SettingsOption::WindowMode,
+    SettingsOption::Resolution,
+    SettingsOption::Vsync,
+    SettingsOption::ScreenEffects,
+    SettingsOption::DamageNumbers,
+    SettingsOption::AimMode,
+];
+
+const RESOLUTIONS: [(u32, u32); 4] = [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+const SLIDER_STEP: f32 = 0.05;
+
+impl SettingsOption {
+    fn label(&self) -> &'static str {
+        match self {
+            SettingsOption::MasterVolume => "Master Volume",
+            SettingsOption::MusicVolume => "Music Volume",
+            SettingsOption::SfxVolume => "SFX Volume",
+            SettingsOption::UiVolume => "Menu Volume",
+            SettingsOption::WindowMode => "Window Mode",
+            SettingsOption::Resolution => "Resolution",
+            SettingsOption::Vsync => "VSync",
+            SettingsOption::ScreenEffects => "Screen Effects",
+            SettingsOption::DamageNumbers => "Damage Numbers",
+            SettingsOption::AimMode => "Aim Mode",
+            SettingsOption::Binding(action) => action.name(),
+            SettingsOption::Back => "Back",
+        }
+    }
+
+    fn kind(&self) -> OptionKind {
+        match self {
+            SettingsOption::MasterVolume
+            | SettingsOption::MusicVolume
+            | SettingsOption::SfxVolume
+            | SettingsOption::UiVolume
+            | SettingsOption::ScreenEffects => OptionKind::Slider,
+            SettingsOption::Vsync | SettingsOption::DamageNumbers => OptionKind::Toggle,
+            SettingsOption::WindowMode | SettingsOption::Resolution | SettingsOption::AimMode => {
+                OptionKind::Dropdown
+            }
+            SettingsOption::Binding(_) => OptionKind::Binding,
+            SettingsOption::Back => OptionKind::Button,
+        }
+    }
+
+    fn slider(&self, settings: &Settings) -> f32 {
+        match self {
+            SettingsOption::MasterVolume => settings.audio.master,
+            SettingsOption::MusicVolume => settings.audio.music,
+            SettingsOption::SfxVolume => settings.audio.sfx,
+            SettingsOption::UiVolume => settings.audio.ui,
+            SettingsOption::ScreenEffects => settings.gameplay.screen_effects,
+            _ => 0.0,
+        }
+    }
+
+    fn set_slider(&self, settings: &mut Settings, value: f32) {
+        // snap to steps so dragging does not write the file every frame
+        let value = ((value / SLIDER_STEP).round() * SLIDER_STEP).clamp(0.0, 1.0);
+        match self {
+            SettingsOption::MasterVolume => settings.audio.master = value,
+            SettingsOption::MusicVolume => settings.audio.music = value,
+            SettingsOption::SfxVolume => settings.audio.sfx = value,
+            SettingsOption::UiVolume => settings.audio.ui = value,
+            SettingsOption::ScreenEffects => settings.gameplay.screen_effects = value,
+            _ => {}
+        }
+    }
+
+    fn toggle(&self, settings: &mut Settings) {
+        match self {
+            SettingsOption::Vsync => settings.video.vsync = !settings.video.vsync,
+            SettingsOption::DamageNumbers => {
+                settings.gameplay.damage_numbers = !settings.gameplay.damage_numbers
+            }
+            _ => {}
+        }
+    }
+
+    fn choices(&self) -> Vec<String> {
+        match self {
+            SettingsOption::WindowMode => ["Windowed", "Fullscreen", "Borderless"]
+                .map(String::from)
+                .to_vec(),
+            SettingsOption::Resolution => RESOLUTIONS
+                .iter()
+                .map(|(width, height)| format!("{}x{}", width, height))
+                .collect(),
+            SettingsOption::AimMode => ["Keys", "Mouse", "Auto"].map(String::from).to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn choice(&self, settings: &Settings) -> usize {
+        match self {
+            SettingsOption::WindowMode => settings.video.window_mode as usize,
+            SettingsOption::Resolution => RESOLUTIONS
+                .iter()
+                .position(|resolution| *resolution == settings.video.resolution)
+                .unwrap_or(0),
+            SettingsOption::AimMode => settings.gameplay.aim_mode as usize,
+            _ => 0,
+        }
+    }
+
+    fn set_choice(&self, settings: &mut Settings, index: usize) {
+        match self {
+            SettingsOption::WindowMode => {
+                settings.video.window_mode = [
+                    WindowModeSetting::Windowed,
+                    WindowModeSetting::Fullscreen,
+                    WindowModeSetting::Borderless,
+                ][index]
+            }
+            SettingsOption::Resolution => settings.video.resolution = RESOLUTIONS[index],
+            SettingsOption::AimMode => {
+                settings.gameplay.aim_mode = [AimMode::Keys, AimMode::Mouse, AimMode::Auto][index]
+            }
+            _ => {}
+        }
+    }
+
+    fn value_text(&self, settings: &Settings, menu: &SettingsMenu) -> String {
+        match self.kind() {
+            OptionKind::Slider => format!("{:.0}%", self.slider(settings) * 100.0),
+            OptionKind::Toggle => {
+                let on = match self {
+                    SettingsOption::Vsync => settings.video.vsync,
+                    _ => settings.gameplay.damage_numbers,
+                };
+                let value = if on { "On" } else { "Off" };
+                value.to_string()
+            }
+            OptionKind::Dropdown => format!("< {} >", self.choices()[self.choice(settings)]),
+            OptionKind::Binding => match self {
+                SettingsOption::Binding(action) if menu.rebinding == Some(*action) => {
+                    "Press a key...".to_string()
+                }
+                SettingsOption::Binding(action) => format!("{:?}", settings.controls.key(*action)),
+                _ => String::new(),
+            },
+            OptionKind::Button => String::new(),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SettingsRow(SettingsOption);
+
+#[derive(Component)]
+pub struct SettingsValueText(SettingsOption);
+
+#[derive(Component)]
+pub struct SliderTrack(SettingsOption);
+
+#[derive(Component)]
+pub struct SliderFill(SettingsOption);
+
+#[derive(Component)]
+pub struct DropdownList;
+
+#[derive(Component)]
+pub struct DropdownItem(usize);
+
+// Spawns and despawns the screen as it is opened and closed
+pub fn sync_settings_menu(
+    mut commands: Commands,
+    menu: Res<SettingsMenu>,
+    settings: Res<Settings>,
+    theme: Res<UiTheme>,
+    root_query: Query<Entity, With<TagSettingsMenu>>,
+) {
+    if !menu.is_changed() {
+        return;
+    }
+
+    match (menu.open, root_query.is_empty()) {
+        (true, true) => spawn_settings_menu(&mut commands, &settings, &menu, &theme),
+        (false, false) => {
+            for entity in &root_query {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn close_settings_menu(
+    mut commands: Commands,
+    mut menu: ResMut<SettingsMenu>,
+    mut focus: ResMut<UiFocus>,
+    root_query: Query<Entity, With<TagSettingsMenu>>,
+) {
+    *menu = SettingsMenu::default();
+    focus.locked = false;
+    for entity in &root_query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn settings_menu_open(menu: Res<SettingsMenu>) -> bool {
+    menu.open
+}
+
+fn spawn_settings_menu(
+    commands: &mut Commands,
+    settings: &Settings,
+    menu: &SettingsMenu,
+    theme: &UiTheme,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                // covers and blocks the menu it was opened from
+                background_color: Color::BLACK.into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            TagSettingsMenu,
+            FocusScope,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Settings",
+                TextStyle {
+                    font_size: 50.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Row,
+                        column_gap: Val::Px(40.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let controls = InputAction::ALL.map(SettingsOption::Binding);
+                    for column in [&GENERAL_OPTIONS[..], &controls[..]] {
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(6.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for option in column {
+                                    spawn_row(parent, *option, settings, menu, theme);
+                                }
+                            });
+                    }
+                });
+
+            spawn_row(parent, SettingsOption::Back, settings, menu, theme);
+        });
+}
+
+fn spawn_row(
+    parent: &mut ChildBuilder,
+    option: SettingsOption,
+    settings: &Settings,
+    menu: &SettingsMenu,
+    theme: &UiTheme,
+) {
+    let text_style = TextStyle {
+        font_size: 22.0,
+        color: theme.foreground.normal,
+        ..default()
+    };
+
+    let mut row = parent.spawn((
+        button_bundle(
+            theme,
+            Style {
+                width: Val::Px(420.0),
+                height: Val::Px(36.0),
+                padding: UiRect::horizontal(Val::Px(10.0)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+        ),
+        SettingsRow(option),
+    ));
+    if let OptionKind::Slider | OptionKind::Toggle | OptionKind::Dropdown = option.kind() {
+        row.insert(Adjustable);
+    }
+
+    row.with_children(|parent| {
+        parent.spawn(TextBundle::from_section(option.label(), text_style.clone()));
+
+        if let OptionKind::Slider = option.kind() {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Px(150.0),
+                            height: Val::Px(12.0),
+                            border: UiRect::all(Val::Px(1.0)),
+                            ..default()
+                        },
+                        background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                        border_color: Color::WHITE.into(),
+                        ..default()
+                    },
+                    // the track reports where it is clicked and dragged
+                    Interaction::default(),
+                    RelativeCursorPosition::default(),
+                    SliderTrack(option),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(option.slider(settings) * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..default()
+                        },
+                        SliderFill(option),
+                    ));
+                });
+        }
+
+        parent.spawn((
+            TextBundle::from_section(option.value_text(settings, menu), text_style),
+            SettingsValueText(option),
+        ));
+    });
+}
+
+fn back_pressed(
+    keyboard_input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_input: &Input<GamepadButton>,
+) -> bool {
+    keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::East))
+        })
+}
+
+pub fn rebind_key(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut focus: ResMut<UiFocus>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(action) = menu.rebinding else {
+        return;
+    };
+    // the key or button that started rebinding is still just pressed
+    if menu.is_changed() {
+        return;
+    }
+
+    // Escape cancels instead of being bound
+    if back_pressed(&keyboard_input, &gamepads, &gamepad_input) {
+        menu.rebinding = None;
+        focus.locked = false;
+        return;
+    }
+
+    if let Some(key) = keyboard_input.get_just_pressed().next() {
+        let mut new_settings = settings.clone();
+        new_settings.controls.bind(action, *key);
+        settings.set_if_neq(new_settings);
+        menu.rebinding = None;
+        focus.locked = false;
+    }
+}
+
+pub fn close_on_back(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut menu: ResMut<SettingsMenu>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    if menu.rebinding.is_some() || !back_pressed(&keyboard_input, &gamepads, &gamepad_input) {
+        return;
+    }
+
+    // an expanded dropdown closes first
+    if menu.dropdown.is_some() {
+        menu.dropdown = None;
+    } else {
+        menu.open = false;
+    }
+    sound_event.send(PlaySoundEffectEvent {
+        sound: SoundEffectType::UIEnter,
+        position: None,
+    });
+}
+
+pub fn click_settings_menu(
+    mut menu: ResMut<SettingsMenu>,
+    mut focus: ResMut<UiFocus>,
+    mut settings: ResMut<Settings>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    mut adjust_events: EventReader<ButtonAdjustEvent>,
+    row_query: Query<&SettingsRow>,
+    item_query: Query<&DropdownItem>,
+    track_query: Query<(&Interaction, &RelativeCursorPosition, &SliderTrack)>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    let mut new_settings = settings.clone();
+    // clicks make their own sound, only adjusting ticks
+    let mut adjusted = false;
+
+    for event in click_events.read() {
+        if let (Some(option), Ok(item)) = (menu.dropdown, item_query.get(event.entity)) {
+            option.set_choice(&mut new_settings, item.0);
+            menu.dropdown = None;
+            continue;
+        }
+
+        let Ok(SettingsRow(option)) = row_query.get(event.entity) else {
+            continue;
+        };
+        // clicking anywhere else closes an expanded dropdown
+        if menu.dropdown.take().is_some() {
+            continue;
+        }
+
+        match (option.kind(), option) {
+            (OptionKind::Toggle, _) => option.toggle(&mut new_settings),
+            (OptionKind::Dropdown, _) => menu.dropdown = Some(*option),
+            (OptionKind::Binding, SettingsOption::Binding(action)) => {
+                menu.rebinding = Some(*action);
+                focus.locked = true;
+            }
+            (OptionKind::Button, _) => menu.open = false,
+            _ => {}
+        }
+    }
+
+    for event in adjust_events.read() {
+        let Ok(SettingsRow(option)) = row_query.get(event.entity) else {
+            continue;
+        };
+        adjusted = true;
+
+        match option.kind() {
+            OptionKind::Slider => {
+                let value = option.slider(&settings) + event.step as f32 * SLIDER_STEP;
+                option.set_slider(&mut new_settings, value);
+            }
+            OptionKind::Toggle => option.toggle(&mut new_settings),
+            OptionKind::Dropdown => {
+                let count = option.choices().len() as i32;
+                let index = (option.choice(&settings) as i32 + event.step).rem_euclid(count);
+                option.set_choice(&mut new_settings, index as usize);
+            }
+            _ => {}
+        }
+    }
+
+    // sliders follow the cursor for as long as the track is held
+    for (interaction, cursor, track) in &track_query {
+        if *interaction != Interaction::Pressed || menu.dropdown.is_some() {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            track.0.set_slider(&mut new_settings, position.x);
+            adjusted = true;
+        }
+    }
+
+    if settings.set_if_neq(new_settings) && adjusted {
+        sound_event.send(PlaySoundEffectEvent {
+            sound: SoundEffectType::UIHover,
+            position: None,
+        });
+    }
+}
+
+// Keeps values and the expanded dropdown in sync with the menu
+pub fn update_settings_menu(
+    mut commands: Commands,
+    menu: Res<SettingsMenu>,
+    settings: Res<Settings>,
+    theme: Res<UiTheme>,
+    row_query: Query<(Entity, &SettingsRow)>,
+    mut text_query: Query<(&SettingsValueText, &mut Text)>,
+    mut fill_query: Query<(&SliderFill, &mut Style)>,
+    list_query: Query<Entity, With<DropdownList>>,
+    mut shown_dropdown: Local<Option<SettingsOption>>,
+) {
+    if !menu.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    for (value, mut text) in &mut text_query {
+        text.sections[0].value = value.0.value_text(&settings, &menu);
+    }
+
+    for (fill, mut style) in &mut fill_query {
+        style.width = Val::Percent(fill.0.slider(&settings) * 100.0);
+    }
+
+    if menu.dropdown == *shown_dropdown {
+        return;
+    }
+    *shown_dropdown = menu.dropdown;
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(option) = menu.dropdown else {
+        return;
+    };
+    if let Some((row, _)) = row_query.iter().find(|(_, row)| row.0 == option) {
+        commands.entity(row).with_children(|parent| {
+            spawn_dropdown_list(parent, option, option.choice(&settings), &theme);
+        });
+    }
+}
+
+fn spawn_dropdown_list(
+    parent: &mut ChildBuilder,
+    option: SettingsOption,
+    selected: usize,
+    theme: &UiTheme,
+) {
+    parent
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Percent(100.0),
+                    right: Val::Px(0.0),
+                    width: Val::Px(200.0),
+                    flex_direction: FlexDirection::Column,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                border_color: theme.border.into(),
+                z_index: ZIndex::Global(30),
+                ..default()
+            },
+            DropdownList,
+            FocusScope,
+        ))
+        .with_children(|parent| {
+            for (i, choice) in option.choices().into_iter().enumerate() {
+                let mut item = parent.spawn((
+                    button_bundle(
+                        theme,
+                        Style {
+                            height: Val::Px(30.0),
+                            padding: UiRect::horizontal(Val::Px(10.0)),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                    ),
+                    DropdownItem(i),
+                ));
+                if i == selected {
+                    item.insert(DefaultFocus);
+                }
+                item.with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        choice,
+                        TextStyle {
+                            font_size: 22.0,
+                            color: theme.foreground.normal,
+                            ..default()
+                        },
+                    ));
+                });
+            }
+        });
+}
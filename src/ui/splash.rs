@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+
+use super::components::TagSplash;
+use crate::game::components::GameState;
+
+const SPLASH_DURATION: f32 = 2.5;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct SplashTimer(Timer);
+
+pub fn setup_splash(mut commands: Commands) {
+    commands.insert_resource(SplashTimer(Timer::from_seconds(
+        SPLASH_DURATION,
+        TimerMode::Once,
+    )));
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::BLACK.into(),
+                ..default()
+            },
+            TagSplash,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Rustpg",
+                TextStyle {
+                    font_size: 80.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+// pressing any key, mouse or gamepad button skips straight to the menu, the
+// release of the click that focused the window does not
+pub fn update_splash(
+    mut timer: ResMut<SplashTimer>,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let skipped = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_input.get_just_pressed().next().is_some();
+
+    if timer.tick(time.delta()).finished() || skipped {
+        next_state.set(GameState::Menu);
+    }
+}
+
+pub fn cleanup_splash(mut commands: Commands, splash_query: Query<Entity, With<TagSplash>>) {
+    for entity in &splash_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<SplashTimer>();
+}
@@ -1,17 +1,11 @@
 use bevy::prelude::*;
 
 use super::components::*;
-use crate::game::components::GameRules;
+use crate::game::{components::GameRules, run::RunScoped};
 
 #[derive(Component)]
 pub struct UIXPBar;
 
-pub fn despawn_game_ui(mut commands: Commands, ui_query: Query<Entity, With<TagGameUI>>) {
-    for entity in &ui_query {
-        commands.entity(entity).despawn_recursive();
-    }
-}
-
 pub fn setup_game_ui(mut commands: Commands) {
     commands
         .spawn((
@@ -32,6 +26,7 @@ pub fn setup_game_ui(mut commands: Commands) {
                 ..default()
             },
             TagGameUI,
+            RunScoped,
         ))
         .with_children(|parent| {
             parent
@@ -1,38 +1,30 @@
 use bevy::prelude::*;
+use rand::seq::SliceRandom;
 
 use crate::{
-    audio::components::{PlaySoundEffectEvent, SoundEffectType},
-    game::components::GameState,
+    game::components::{GameRules, GameState},
+    upgrade::components::{all_upgrades, UpgradeTakenEvent},
 };
 
-use super::components::{ButtonStyle, TagUpgradeMenu, UpgradeButtonAction};
+use super::{
+    components::{TagUpgradeMenu, UpgradeCard},
+    widgets::{button_bundle, button_style, ButtonClickEvent, Disabled, UiTheme},
+};
 
-#[derive(Bundle)]
-pub struct UpgradeCardBundle {
-    node: ButtonBundle,
-}
+// Cards can't be picked right away, a click or key meant for the game should
+// not pick an upgrade the player never saw
+const PICK_DELAY: f32 = 0.5;
 
-impl UpgradeCardBundle {
-    fn new() -> UpgradeCardBundle {
-        UpgradeCardBundle {
-            node: ButtonBundle {
-                style: Style {
-                    width: Val::Px(300.0),
-                    height: Val::Px(500.0),
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    border: UiRect::all(Val::Px(2.0)),
-                    ..default()
-                },
-                background_color: Color::BLACK.into(),
-                border_color: Color::WHITE.into(),
-                ..default()
-            },
-        }
-    }
+const CARD_COUNT: usize = 3;
+
+#[derive(Component)]
+pub struct UpgradeCardDelay(Timer);
+
+pub fn setup_menu(mut commands: Commands, theme: Res<UiTheme>) {
+    spawn_menu(&mut commands, &theme);
 }
 
-pub fn setup_menu(mut commands: Commands) {
+fn spawn_menu(commands: &mut Commands, theme: &UiTheme) {
     commands
         .spawn((
             NodeBundle {
@@ -49,85 +41,91 @@ pub fn setup_menu(mut commands: Commands) {
                 ..default()
             },
             TagUpgradeMenu,
+            UpgradeCardDelay(Timer::from_seconds(PICK_DELAY, TimerMode::Once)),
         ))
         .with_children(|parent| {
-            parent
-                .spawn((UpgradeCardBundle::new(), UpgradeButtonAction::Upgrade1))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Upgrade 1",
-                        TextStyle {
-                            font_size: 30.0,
-                            color: Color::WHITE,
-                            ..default()
-                        },
-                    ));
-                });
-
-            parent
-                .spawn((UpgradeCardBundle::new(), UpgradeButtonAction::Upgrade2))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Upgrade 2",
-                        TextStyle {
-                            font_size: 30.0,
-                            color: Color::WHITE,
-                            ..default()
-                        },
-                    ));
-                });
+            // a different random pick of upgrades every time
+            let mut upgrades = all_upgrades();
+            upgrades.shuffle(&mut rand::thread_rng());
+            upgrades.truncate(CARD_COUNT);
 
-            parent
-                .spawn((UpgradeCardBundle::new(), UpgradeButtonAction::Upgrade3))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        "Upgrade 3",
-                        TextStyle {
-                            font_size: 30.0,
-                            color: Color::WHITE,
-                            ..default()
-                        },
-                    ));
-                });
+            for upgrade in upgrades {
+                parent
+                    .spawn((button_bundle(theme, button_style(300.0, 500.0)), Disabled))
+                    .with_children(|parent| {
+                        parent.spawn(
+                            TextBundle::from_sections([
+                                TextSection::new(
+                                    format!("{}\n\n", upgrade.name),
+                                    TextStyle {
+                                        font_size: theme.font_size,
+                                        color: theme.foreground.disabled,
+                                        ..default()
+                                    },
+                                ),
+                                TextSection::new(
+                                    upgrade.description.clone(),
+                                    TextStyle {
+                                        font_size: theme.font_size * 0.6,
+                                        color: theme.foreground.disabled,
+                                        ..default()
+                                    },
+                                ),
+                            ])
+                            .with_text_alignment(TextAlignment::Center),
+                        );
+                    })
+                    .insert(UpgradeCard(upgrade));
+            }
         });
 }
 
+pub fn enable_cards(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut menu_query: Query<(&mut UpgradeCardDelay, &Children)>,
+) {
+    for (mut delay, children) in &mut menu_query {
+        if delay.0.tick(time.delta()).just_finished() {
+            for child in children {
+                commands.entity(*child).remove::<Disabled>();
+            }
+        }
+    }
+}
+
 pub fn update_menu(
+    mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
-    mut interaction_query: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<Button>),
-    >,
-    mut text_query: Query<&mut Text>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    mut game: ResMut<GameRules>,
+    theme: Res<UiTheme>,
+    menu_query: Query<Entity, With<TagUpgradeMenu>>,
+    mut click_events: EventReader<ButtonClickEvent>,
+    card_query: Query<&UpgradeCard>,
+    mut upgrade_event: EventWriter<UpgradeTakenEvent>,
 ) {
-    for (interaction, mut color, children) in &mut interaction_query {
-        for child in children {
-            let text = text_query.get_mut(*child);
-            if let Ok(mut text) = text {
-                match *interaction {
-                    Interaction::Pressed => {
-                        *color = ButtonStyle::default().background.active;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.active;
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::UIEnter,
-                        });
-                        next_state.set(GameState::Playing);
-                    }
-                    Interaction::Hovered => {
-                        *color = ButtonStyle::default().background.hover;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.hover;
-                        sound_event.send(PlaySoundEffectEvent {
-                            sound: SoundEffectType::UIHover,
-                        });
-                    }
-                    Interaction::None => {
-                        *color = ButtonStyle::default().background.default;
-                        text.sections[0].style.color = ButtonStyle::default().foreground.default;
-                    }
-                }
-            }
+    // only the first pick counts, the menu is rebuilt or closed right after it
+    let Some(UpgradeCard(upgrade)) = click_events
+        .read()
+        .find_map(|event| card_query.get(event.entity).ok())
+    else {
+        return;
+    };
+
+    upgrade_event.send(UpgradeTakenEvent {
+        name: upgrade.name.clone(),
+        upgrade_type: upgrade.upgrade_type.clone(),
+    });
+
+    game.pending_level_ups = game.pending_level_ups.saturating_sub(1);
+    if game.pending_level_ups > 0 {
+        // more level ups queued, offer a fresh set of upgrades
+        for entity in &menu_query {
+            commands.entity(entity).despawn_recursive();
         }
+        spawn_menu(&mut commands, &theme);
+    } else {
+        next_state.set(GameState::Playing);
     }
 }
 
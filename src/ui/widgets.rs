@@ -0,0 +1,503 @@
+use bevy::prelude::*;
+
+use crate::audio::components::{PlaySoundEffectEvent, SoundEffectType};
+
+pub struct WidgetPlugin;
+
+impl Plugin for WidgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiTheme>()
+            .init_resource::<UiFocus>()
+            .add_event::<ButtonClickEvent>()
+            .add_event::<ButtonAdjustEvent>()
+            .add_systems(
+                Update,
+                (
+                    track_focus_scopes,
+                    navigate_focus,
+                    focus_on_hover,
+                    click_buttons,
+                    style_buttons,
+                )
+                    .chain()
+                    .in_set(WidgetSet),
+            );
+    }
+}
+
+/// Menus read `ButtonClickEvent`s after this set
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WidgetSet;
+
+/// Colors and sizes shared by every menu
+#[derive(Resource)]
+pub struct UiTheme {
+    pub background: WidgetColors,
+    pub foreground: WidgetColors,
+    pub border: Color,
+    pub font_size: f32,
+}
+
+pub struct WidgetColors {
+    pub normal: Color,
+    pub focused: Color,
+    pub pressed: Color,
+    pub disabled: Color,
+}
+
+impl Default for UiTheme {
+    fn default() -> Self {
+        Self {
+            background: WidgetColors {
+                normal: Color::BLACK,
+                focused: Color::WHITE,
+                pressed: Color::rgb(0.5, 0.5, 0.5),
+                disabled: Color::rgb(0.1, 0.1, 0.1),
+            },
+            foreground: WidgetColors {
+                normal: Color::WHITE,
+                focused: Color::BLACK,
+                pressed: Color::BLACK,
+                disabled: Color::rgb(0.4, 0.4, 0.4),
+            },
+            border: Color::WHITE,
+            font_size: 30.0,
+        }
+    }
+}
+
+impl WidgetColors {
+    fn get(&self, state: WidgetState) -> Color {
+        match state {
+            WidgetState::Normal => self.normal,
+            WidgetState::Focused => self.focused,
+            WidgetState::Pressed => self.pressed,
+            WidgetState::Disabled => self.disabled,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum WidgetState {
+    Normal,
+    Focused,
+    Pressed,
+    Disabled,
+}
+
+/// Can be reached with the keyboard or gamepad, styled from the `UiTheme`
+#[derive(Component)]
+pub struct Focusable;
+
+/// Left and right send a `ButtonAdjustEvent` instead of moving focus, for
+/// sliders and selectors
+#[derive(Component)]
+pub struct Adjustable;
+
+/// Greyed out, can't be focused or clicked
+#[derive(Component)]
+pub struct Disabled;
+
+/// Gets focus when its menu or scope opens, instead of the first button
+#[derive(Component)]
+pub struct DefaultFocus;
+
+/// While one exists, focus stays inside the latest one, like a modal. When it
+/// goes away focus returns to where it was before.
+#[derive(Component)]
+pub struct FocusScope;
+
+#[derive(Component, Clone, Copy)]
+pub struct ButtonSounds {
+    pub focus: Option<SoundEffectType>,
+    pub click: Option<SoundEffectType>,
+}
+
+impl ButtonSounds {
+    pub const SILENT: ButtonSounds = ButtonSounds {
+        focus: None,
+        click: None,
+    };
+}
+
+impl Default for ButtonSounds {
+    fn default() -> Self {
+        Self {
+            focus: Some(SoundEffectType::UIHover),
+            click: Some(SoundEffectType::UIEnter),
+        }
+    }
+}
+
+/// A focusable button was clicked or confirmed with Enter or the south button
+#[derive(Event)]
+pub struct ButtonClickEvent {
+    pub entity: Entity,
+}
+
+/// Left (-1) or right (1) on a focused `Adjustable`
+#[derive(Event)]
+pub struct ButtonAdjustEvent {
+    pub entity: Entity,
+    pub step: i32,
+}
+
+#[derive(Resource, Default)]
+pub struct UiFocus {
+    focused: Option<Entity>,
+    // open scopes with the focus to return to when each one closes
+    scopes: Vec<(Entity, Option<Entity>)>,
+    /// Set while a menu wants raw input for itself, like when binding a key
+    pub locked: bool,
+}
+
+impl UiFocus {
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused
+    }
+
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.focused == Some(entity)
+    }
+}
+
+pub fn button_style(width: f32, height: f32) -> Style {
+    Style {
+        width: Val::Px(width),
+        height: Val::Px(height),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        border: UiRect::all(Val::Px(2.0)),
+        ..default()
+    }
+}
+
+/// A themed button without content, for buttons with their own layout
+pub fn button_bundle(theme: &UiTheme, style: Style) -> impl Bundle {
+    (
+        ButtonBundle {
+            style,
+            background_color: theme.background.normal.into(),
+            border_color: theme.border.into(),
+            ..default()
+        },
+        Focusable,
+        ButtonSounds::default(),
+    )
+}
+
+/// A themed button with a label. `extra` usually is the action it stands for,
+/// it may also replace the default `ButtonSounds`.
+pub fn spawn_button(
+    parent: &mut ChildBuilder,
+    theme: &UiTheme,
+    label: &str,
+    style: Style,
+    extra: impl Bundle,
+) {
+    parent
+        .spawn(button_bundle(theme, style))
+        .insert(extra)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font_size: theme.font_size,
+                    color: theme.foreground.normal,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn track_focus_scopes(
+    mut focus: ResMut<UiFocus>,
+    added_query: Query<Entity, Added<FocusScope>>,
+    mut removed: RemovedComponents<FocusScope>,
+) {
+    for entity in removed.read() {
+        if let Some(index) = focus.scopes.iter().position(|(scope, _)| *scope == entity) {
+            let (_, previous) = focus.scopes.remove(index);
+            if index == focus.scopes.len() {
+                focus.focused = previous;
+            }
+        }
+    }
+
+    for entity in &added_query {
+        let previous = focus.focused.take();
+        focus.scopes.push((entity, previous));
+    }
+}
+
+fn in_scope(entity: Entity, scope: Option<Entity>, parent_query: &Query<&Parent>) -> bool {
+    let Some(scope) = scope else {
+        return true;
+    };
+    parent_query
+        .iter_ancestors(entity)
+        .any(|ancestor| ancestor == scope)
+}
+
+#[derive(Default)]
+struct NavigationInput {
+    direction: Option<Vec2>,
+    tab: Option<bool>,
+}
+
+fn read_navigation(
+    keyboard_input: &Input<KeyCode>,
+    gamepads: &Gamepads,
+    gamepad_input: &Input<GamepadButton>,
+) -> NavigationInput {
+    let button = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+
+    // ui space, y points down
+    let direction = if keyboard_input.just_pressed(KeyCode::Up) || button(GamepadButtonType::DPadUp)
+    {
+        Some(Vec2::NEG_Y)
+    } else if keyboard_input.just_pressed(KeyCode::Down) || button(GamepadButtonType::DPadDown) {
+        Some(Vec2::Y)
+    } else if keyboard_input.just_pressed(KeyCode::Left) || button(GamepadButtonType::DPadLeft) {
+        Some(Vec2::NEG_X)
+    } else if keyboard_input.just_pressed(KeyCode::Right) || button(GamepadButtonType::DPadRight) {
+        Some(Vec2::X)
+    } else {
+        None
+    };
+
+    let tab = keyboard_input
+        .just_pressed(KeyCode::Tab)
+        .then(|| !keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]));
+
+    NavigationInput { direction, tab }
+}
+
+// closest candidate in the direction, preferring ones in line with the current
+fn closest_in_direction(
+    from: Vec2,
+    direction: Vec2,
+    candidates: &[(Entity, Vec2)],
+) -> Option<Entity> {
+    candidates
+        .iter()
+        .filter_map(|(entity, position)| {
+            let delta = *position - from;
+            let along = delta.dot(direction);
+            let across = delta.perp_dot(direction).abs();
+            (along > 0.5).then_some((*entity, along + across * 2.0))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+fn navigate_focus(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut focus: ResMut<UiFocus>,
+    focusable_query: Query<
+        (Entity, &Node, &GlobalTransform, &InheritedVisibility),
+        (With<Focusable>, Without<Disabled>),
+    >,
+    parent_query: Query<&Parent>,
+    default_query: Query<(), With<DefaultFocus>>,
+    adjustable_query: Query<(), With<Adjustable>>,
+    sounds_query: Query<&ButtonSounds>,
+    mut adjust_event: EventWriter<ButtonAdjustEvent>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    let scope = focus.scopes.last().map(|(scope, _)| *scope);
+
+    // in reading order, nodes that were not laid out yet have no size
+    let mut candidates: Vec<(Entity, Vec2)> = focusable_query
+        .iter()
+        .filter(|(entity, node, _, visibility)| {
+            visibility.get() && node.size() != Vec2::ZERO && in_scope(*entity, scope, &parent_query)
+        })
+        .map(|(entity, _, transform, _)| (entity, transform.translation().truncate()))
+        .collect();
+    candidates.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let current = focus
+        .focused
+        .and_then(|focused| candidates.iter().position(|(entity, _)| *entity == focused));
+
+    let Some(current) = current else {
+        // nothing focused yet, start at the default or the top without a sound
+        let first = candidates
+            .iter()
+            .find(|(entity, _)| default_query.contains(*entity))
+            .or(candidates.first())
+            .map(|(entity, _)| *entity);
+        if focus.focused != first {
+            focus.focused = first;
+        }
+        return;
+    };
+
+    if focus.locked {
+        return;
+    }
+
+    let input = read_navigation(&keyboard_input, &gamepads, &gamepad_input);
+    let (focused, position) = candidates[current];
+
+    let next = if let Some(forward) = input.tab {
+        let count = candidates.len();
+        let index = if forward {
+            (current + 1) % count
+        } else {
+            (current + count - 1) % count
+        };
+        Some(candidates[index].0)
+    } else if let Some(direction) = input.direction {
+        if direction.y == 0.0 && adjustable_query.contains(focused) {
+            adjust_event.send(ButtonAdjustEvent {
+                entity: focused,
+                step: direction.x as i32,
+            });
+            None
+        } else {
+            closest_in_direction(position, direction, &candidates)
+        }
+    } else {
+        None
+    };
+
+    if let Some(next) = next.filter(|next| *next != focused) {
+        focus.focused = Some(next);
+        if let Some(sound) = sounds_query.get(next).ok().and_then(|sounds| sounds.focus) {
+            sound_event.send(PlaySoundEffectEvent {
+                sound,
+                position: None,
+            });
+        }
+    }
+}
+
+// The mouse moves focus too, so there is only ever one highlighted button
+fn focus_on_hover(
+    mut focus: ResMut<UiFocus>,
+    interaction_query: Query<
+        (Entity, &Interaction, &ButtonSounds),
+        (Changed<Interaction>, With<Focusable>, Without<Disabled>),
+    >,
+    parent_query: Query<&Parent>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    if focus.locked {
+        return;
+    }
+
+    let scope = focus.scopes.last().map(|(scope, _)| *scope);
+    for (entity, interaction, sounds) in &interaction_query {
+        if *interaction == Interaction::Hovered
+            && !focus.is_focused(entity)
+            && in_scope(entity, scope, &parent_query)
+        {
+            focus.focused = Some(entity);
+            if let Some(sound) = sounds.focus {
+                sound_event.send(PlaySoundEffectEvent {
+                    sound,
+                    position: None,
+                });
+            }
+        }
+    }
+}
+
+fn click_buttons(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    focus: Res<UiFocus>,
+    interaction_query: Query<
+        (Entity, &Interaction),
+        (Changed<Interaction>, With<Focusable>, Without<Disabled>),
+    >,
+    button_query: Query<&ButtonSounds, (With<Focusable>, Without<Disabled>)>,
+    mut click_event: EventWriter<ButtonClickEvent>,
+    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+) {
+    if focus.locked {
+        return;
+    }
+
+    let confirm = keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::Space])
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South))
+        });
+
+    let clicked = interaction_query
+        .iter()
+        .filter(|(_, interaction)| **interaction == Interaction::Pressed)
+        .map(|(entity, _)| entity)
+        .chain(focus.focused.filter(|_| confirm));
+
+    for entity in clicked {
+        let Ok(sounds) = button_query.get(entity) else {
+            continue;
+        };
+        if let Some(sound) = sounds.click {
+            sound_event.send(PlaySoundEffectEvent {
+                sound,
+                position: None,
+            });
+        }
+        click_event.send(ButtonClickEvent { entity });
+    }
+}
+
+fn style_buttons(
+    theme: Res<UiTheme>,
+    focus: Res<UiFocus>,
+    mut button_query: Query<
+        (
+            Entity,
+            &Interaction,
+            Has<Disabled>,
+            &mut BackgroundColor,
+            &Children,
+        ),
+        With<Focusable>,
+    >,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, interaction, disabled, mut background, children) in &mut button_query {
+        let state = if disabled {
+            WidgetState::Disabled
+        } else if *interaction == Interaction::Pressed {
+            WidgetState::Pressed
+        } else if focus.is_focused(entity) {
+            WidgetState::Focused
+        } else {
+            WidgetState::Normal
+        };
+
+        // only write on a change, or every button would be marked as changed
+        let color = theme.background.get(state);
+        if background.0 != color {
+            background.0 = color;
+        }
+
+        let color = theme.foreground.get(state);
+        for child in children {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                if text
+                    .sections
+                    .iter()
+                    .any(|section| section.style.color != color)
+                {
+                    for section in &mut text.sections {
+                        section.style.color = color;
+                    }
+                }
+            }
+        }
+    }
+}
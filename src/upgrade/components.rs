@@ -1,19 +1,91 @@
 use bevy::prelude::*;
 
+use crate::player::components::PlayerStats;
+
+#[derive(Clone, Debug)]
 pub struct Upgrade {
     pub name: String,
     pub description: String,
     pub upgrade_type: UpgradeType,
 }
 
+// How much an upgrade adds to each player stat, negative shot speed shoots faster
+#[derive(Clone, Debug, Default)]
 pub struct UpgradeType {
-    damage: f32,
-    move_speed: f32,
-    shot_speed: f32,
-    effect: EffectType,
+    pub damage: f32,
+    pub move_speed: f32,
+    pub shot_speed: f32,
+    pub pickup_radius: f32,
+    pub pickup_strength: f32,
+}
+
+#[derive(Event)]
+pub struct UpgradeTakenEvent {
+    pub name: String,
+    pub upgrade_type: UpgradeType,
+}
+
+impl UpgradeType {
+    pub fn apply(&self, stats: &mut PlayerStats) {
+        stats.bullet_damage += self.damage;
+        stats.move_speed += self.move_speed;
+        // never so fast the shoot timer stops
+        stats.shot_speed = (stats.shot_speed + self.shot_speed).max(0.05);
+        stats.pickup_radius += self.pickup_radius;
+        stats.pickup_strength += self.pickup_strength;
+    }
+}
+
+fn upgrade(name: &str, description: &str, upgrade_type: UpgradeType) -> Upgrade {
+    Upgrade {
+        name: name.to_string(),
+        description: description.to_string(),
+        upgrade_type,
+    }
 }
 
-pub enum EffectType {
-    Fire,
-    Ice,
+/// Every upgrade a level up can offer
+pub fn all_upgrades() -> Vec<Upgrade> {
+    vec![
+        upgrade(
+            "Magnet",
+            "Pick up xp from further away",
+            UpgradeType {
+                pickup_radius: 50.0,
+                ..default()
+            },
+        ),
+        upgrade(
+            "Tractor Beam",
+            "Collected xp flies in faster",
+            UpgradeType {
+                pickup_strength: 2.0,
+                ..default()
+            },
+        ),
+        upgrade(
+            "Sharp Shots",
+            "Bullets deal more damage",
+            UpgradeType {
+                damage: 0.5,
+                ..default()
+            },
+        ),
+        upgrade(
+            "Quick Feet",
+            "Move faster",
+            UpgradeType {
+                move_speed: 40.0,
+                ..default()
+            },
+        ),
+        upgrade(
+            "Rapid Fire",
+            "Shoot more often",
+            UpgradeType {
+                shot_speed: -0.05,
+                ..default()
+            },
+        ),
+    ]
 }
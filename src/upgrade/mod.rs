@@ -1,7 +1,5 @@
 use bevy::prelude::*;
 
-use crate::game::components::GameState;
-
 pub mod components;
 pub mod systems;
 
@@ -9,6 +7,7 @@ pub struct UpgradePlugin;
 
 impl Plugin for UpgradePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Playing), systems::setup_upgrades);
+        app.add_event::<components::UpgradeTakenEvent>()
+            .add_systems(Update, systems::apply_upgrades);
     }
 }
@@ -1,7 +1,64 @@
 use bevy::prelude::*;
 
 use super::components::*;
+use crate::player::components::Player;
 
-pub fn setup_upgrades(mut commands: Commands) {
-    println!("Setting up upgrades");
+// Changing the stats marks the player changed, the xp collector resizes from that
+pub fn apply_upgrades(
+    mut upgrade_events: EventReader<UpgradeTakenEvent>,
+    mut player_query: Query<&mut Player>,
+) {
+    for event in upgrade_events.read() {
+        println!("Upgrade taken: {}", event.name);
+        for mut player in &mut player_query {
+            event.upgrade_type.apply(&mut player.stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_rapier2d::prelude::Collider;
+
+    use super::*;
+    use crate::{
+        game::tuning::PlayerTuning,
+        xp::{components::XPCollector, systems::resize_xp_collector},
+    };
+
+    #[test]
+    fn pickup_upgrades_grow_the_xp_collector() {
+        let mut app = App::new();
+        app.add_event::<UpgradeTakenEvent>()
+            .add_systems(Update, (apply_upgrades, resize_xp_collector).chain());
+
+        let tuning = PlayerTuning::default();
+        let collector = app
+            .world
+            .spawn((Collider::ball(tuning.pickup_radius), XPCollector))
+            .id();
+        app.world
+            .spawn(Player::new(&tuning))
+            .push_children(&[collector]);
+        app.update();
+
+        let magnet = all_upgrades()
+            .into_iter()
+            .find(|upgrade| upgrade.upgrade_type.pickup_radius > 0.0)
+            .unwrap();
+        app.world.send_event(UpgradeTakenEvent {
+            name: magnet.name,
+            upgrade_type: magnet.upgrade_type.clone(),
+        });
+        app.update();
+
+        let radius = app
+            .world
+            .get::<Collider>(collector)
+            .and_then(|collider| collider.as_ball().map(|ball| ball.radius()));
+        assert_eq!(
+            radius,
+            Some(tuning.pickup_radius + magnet.upgrade_type.pickup_radius)
+        );
+    }
 }
@@ -1,22 +1,24 @@
-use bevy::{
-    prelude::*,
-    window::{PresentMode, WindowTheme},
-};
+use bevy::{prelude::*, window::WindowTheme};
+
+use crate::settings::{resources::Settings, systems::configure_window};
 
 pub struct CustomWindowPlugin;
 
 impl Plugin for CustomWindowPlugin {
     fn build(&self, app: &mut App) {
+        let mut window = Window {
+            title: "bevyaac".into(),
+            prevent_default_event_handling: false,
+            window_theme: Some(WindowTheme::Dark),
+            ..default()
+        };
+        // SettingsPlugin has to be added first
+        configure_window(&mut window, &app.world.resource::<Settings>().video);
+
         app.add_plugins(
             DefaultPlugins
                 .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "bevyaac".into(),
-                        present_mode: PresentMode::Immediate,
-                        prevent_default_event_handling: false,
-                        window_theme: Some(WindowTheme::Dark),
-                        ..default()
-                    }),
+                    primary_window: Some(window),
                     ..default()
                 })
                 .set(ImagePlugin::default_nearest()),
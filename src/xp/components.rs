@@ -15,8 +15,24 @@ pub struct XPDropEvent {
     pub location: Vec3,
 }
 
+#[derive(Event)]
+pub struct XPCollectedEvent {
+    pub amount: u32,
+}
+
 #[derive(Component)]
 pub struct XPCollector;
 
 #[derive(Component)]
 pub struct CollectionAnimation;
+
+// Marks an xp orb that triggers the magnet when collected
+#[derive(Component)]
+pub struct MagnetPickup;
+
+// Pulls in every xp orb on the map until the timer runs out
+#[derive(Component, Deref, DerefMut)]
+pub struct Magnet(pub Timer);
+
+pub const MAGNET_DROP_CHANCE: f32 = 0.01;
+pub const MAGNET_DURATION: f32 = 3.0;
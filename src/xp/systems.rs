@@ -1,7 +1,10 @@
 use crate::{
-    audio::components::{PlaySoundEffectEvent, SoundEffectType},
+    audio::components::{SoundCue, SoundCueEvent},
     base::components::Collectable,
-    game::components::{GameRules, GameState},
+    game::{
+        components::{GameRules, GameState},
+        run::RunScoped,
+    },
     player::components::Player,
 };
 
@@ -17,27 +20,35 @@ impl Plugin for XPPlugin {
             Update,
             (
                 spawn_xp,
-                (collect_xp, move_xp_to_player)
+                (
+                    resize_xp_collector,
+                    collect_xp,
+                    magnet_xp,
+                    move_xp_to_player,
+                )
                     .chain()
                     .run_if(in_state(GameState::Playing)),
             ),
         )
-        .add_event::<XPDropEvent>();
+        .add_event::<XPDropEvent>()
+        .add_event::<XPCollectedEvent>();
     }
 }
 
-fn spawn_xp(mut commands: Commands, mut event_xp_dropped: EventReader<XPDropEvent>) {
+pub fn spawn_xp(mut commands: Commands, mut event_xp_dropped: EventReader<XPDropEvent>) {
     for event in event_xp_dropped.read() {
-        commands.spawn((
+        let magnet = rand::random::<f32>() < MAGNET_DROP_CHANCE;
+        let color = if magnet {
+            Color::rgba(0.3, 0.6, 1.0, 1.0)
+        } else {
+            Color::rgba(1.0, 1.0, 1.0, 0.5)
+        };
+
+        let mut xp = commands.spawn((
             SpriteBundle {
                 sprite: Sprite {
                     custom_size: Some(Vec2::new(10.0, 10.0)),
-                    color: Color::Rgba {
-                        red: (1.0),
-                        green: (1.0),
-                        blue: (1.0),
-                        alpha: (0.5),
-                    },
+                    color,
                     ..default()
                 },
                 transform: Transform::from_translation(event.location),
@@ -45,9 +56,30 @@ fn spawn_xp(mut commands: Commands, mut event_xp_dropped: EventReader<XPDropEven
             },
             XP(event.amount),
             Collectable,
-            Collider::ball(100.0),
+            Collider::ball(5.0),
             Sensor,
+            RunScoped,
         ));
+
+        if magnet {
+            xp.insert(MagnetPickup);
+        }
+    }
+}
+
+pub fn resize_xp_collector(
+    player_query: Query<(&Player, &Children), Changed<Player>>,
+    mut collector_query: Query<&mut Collider, With<XPCollector>>,
+) {
+    for (player, children) in &player_query {
+        for child in children {
+            if let Ok(mut collider) = collector_query.get_mut(*child) {
+                let radius = player.stats.pickup_radius;
+                if collider.as_ball().map(|ball| ball.radius()) != Some(radius) {
+                    *collider = Collider::ball(radius);
+                }
+            }
+        }
     }
 }
 
@@ -69,36 +101,69 @@ fn collect_xp(
     }
 }
 
+fn magnet_xp(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut Magnet), With<Player>>,
+    xp_query: Query<Entity, (With<XP>, Without<CollectionAnimation>)>,
+    time: Res<Time>,
+) {
+    for (player_entity, mut magnet) in &mut player_query {
+        if magnet.tick(time.delta()).finished() {
+            commands.entity(player_entity).remove::<Magnet>();
+            continue;
+        }
+
+        for xp_entity in &xp_query {
+            commands.entity(xp_entity).insert(CollectionAnimation);
+        }
+    }
+}
+
 fn move_xp_to_player(
     mut commands: Commands,
-    mut xp_query: Query<(Entity, &XP, &mut Transform), With<CollectionAnimation>>,
-    player_query: Query<(&Player, &Transform), Without<XP>>,
-    mut sound_event: EventWriter<PlaySoundEffectEvent>,
+    mut xp_query: Query<
+        (Entity, &XP, &mut Transform, Option<&MagnetPickup>),
+        With<CollectionAnimation>,
+    >,
+    player_query: Query<(Entity, &Player, &Transform), Without<XP>>,
+    mut sound_event: EventWriter<SoundCueEvent>,
+    mut collected_event: EventWriter<XPCollectedEvent>,
     mut game: ResMut<GameRules>,
     time: Res<Time>,
 ) {
-    for (xp_entity, xp, mut xp_transform) in &mut xp_query {
-        for (player, player_transform) in &player_query {
-            let acceleration = 5.0;
+    for (xp_entity, xp, mut xp_transform, magnet_pickup) in &mut xp_query {
+        for (player_entity, player, player_transform) in &player_query {
             let smoothness = 0.9;
             let direction = xp_transform.translation - player_transform.translation;
-            let acceleration = direction * acceleration;
+            let acceleration = direction * player.stats.pickup_strength;
             let distance = direction.length();
             // Apply smoothing
             let velocity = acceleration * time.delta_seconds();
             let velocity = velocity * (distance / 35.0);
 
             if distance > player.stats.size + 40.0 {
-                xp_transform.translation -= velocity / smoothness;
+                // never overshoot the player, orbs pulled in from far away move very fast
+                xp_transform.translation -= (velocity / smoothness).clamp_length_max(distance);
                 // let xp shrink as it gets closer to the player
                 let scale = distance / 100.0;
                 xp_transform.scale = Vec3::splat(scale);
             } else {
-                sound_event.send(PlaySoundEffectEvent {
-                    sound: SoundEffectType::XPCollect,
+                sound_event.send(SoundCueEvent {
+                    cue: SoundCue::XPCollected,
+                    position: None,
                 });
                 commands.entity(xp_entity).despawn();
-                game.xp += xp.0;
+                game.add_xp(xp.0);
+                collected_event.send(XPCollectedEvent { amount: xp.0 });
+
+                if magnet_pickup.is_some() {
+                    commands
+                        .entity(player_entity)
+                        .insert(Magnet(Timer::from_seconds(
+                            MAGNET_DURATION,
+                            TimerMode::Once,
+                        )));
+                }
             }
         }
     }